@@ -1,13 +1,29 @@
 use soroban_sdk::{Address, Env};
 
-use crate::storage_types::DataKey;
+use crate::error::ProjectsError;
+use crate::storage_types::{DataKey, PendingAdmin};
 
-pub fn read_administrator(e: &Env) -> Address {
+pub fn read_administrator(e: &Env) -> Result<Address, ProjectsError> {
     let key = DataKey::Admin;
-    e.storage().instance().get(&key).unwrap()
+    e.storage().instance().get(&key).ok_or(ProjectsError::NotInitialized)
 }
 
 pub fn write_administrator(e: &Env, id: &Address) {
     let key = DataKey::Admin;
     e.storage().instance().set(&key, id);
-}
\ No newline at end of file
+}
+
+pub fn read_pending_administrator(e: &Env) -> Option<PendingAdmin> {
+    let key = DataKey::PendingAdmin;
+    e.storage().instance().get(&key)
+}
+
+pub fn write_pending_administrator(e: &Env, pending: &PendingAdmin) {
+    let key = DataKey::PendingAdmin;
+    e.storage().instance().set(&key, pending);
+}
+
+pub fn remove_pending_administrator(e: &Env) {
+    let key = DataKey::PendingAdmin;
+    e.storage().instance().remove(&key);
+}
@@ -0,0 +1,201 @@
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+use crate::error::ProjectsError;
+use crate::category;
+use crate::conflict;
+use crate::project;
+use crate::role;
+use crate::storage_types::{DataKey, ProjectData, Role};
+use crate::storage_types::{PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD};
+
+pub fn get_assignees(e: &Env, project_hash: &BytesN<32>) -> Vec<Address> {
+    let key = DataKey::Assignees(project_hash.clone());
+    if let Some(assignees) = e.storage().persistent().get::<_, Vec<Address>>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        assignees
+    } else {
+        Vec::new(e)
+    }
+}
+
+// projects without assignees can be reviewed by every reviewer
+pub fn is_allowed(e: &Env, project_hash: &BytesN<32>, reviewer: &Address) -> bool {
+    let assignees = get_assignees(e, project_hash);
+    assignees.is_empty() || assignees.contains(reviewer)
+}
+
+pub fn get_number_of_assigned_projects(e: &Env, reviewer: &Address) -> u32 {
+    let key = DataKey::AssignedCount(reviewer.clone());
+    if let Some(count) = e.storage().persistent().get::<_, u32>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        count
+    } else {
+        0
+    }
+}
+
+fn write_number_of_assigned_projects(e: &Env, reviewer: &Address, count: u32) {
+    let key = DataKey::AssignedCount(reviewer.clone());
+    if count == 0 {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    e.storage().persistent().set(&key, &count);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+fn push_to_queue(e: &Env, reviewer: &Address, project_hash: &BytesN<32>) {
+    let position_key = DataKey::AssignedPosition(reviewer.clone(), project_hash.clone());
+    if e.storage().persistent().has(&position_key) {
+        return;
+    }
+    let count = get_number_of_assigned_projects(e, reviewer);
+    let index_key = DataKey::AssignedProject(reviewer.clone(), count);
+    e.storage().persistent().set(&index_key, project_hash);
+    e.storage()
+        .persistent()
+        .extend_ttl(&index_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    e.storage().persistent().set(&position_key, &count);
+    e.storage()
+        .persistent()
+        .extend_ttl(&position_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    write_number_of_assigned_projects(e, reviewer, count + 1);
+}
+
+fn remove_from_queue(e: &Env, reviewer: &Address, project_hash: &BytesN<32>) {
+    let position_key = DataKey::AssignedPosition(reviewer.clone(), project_hash.clone());
+    let position = match e.storage().persistent().get::<_, u32>(&position_key) {
+        Some(position) => position,
+        None => return,
+    };
+    // move the last project of the queue into the freed position
+    let last = get_number_of_assigned_projects(e, reviewer) - 1;
+    if position != last {
+        let last_key = DataKey::AssignedProject(reviewer.clone(), last);
+        let last_hash: BytesN<32> = e.storage().persistent().get(&last_key).unwrap();
+        let index_key = DataKey::AssignedProject(reviewer.clone(), position);
+        e.storage().persistent().set(&index_key, &last_hash);
+        e.storage()
+            .persistent()
+            .extend_ttl(&index_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        let last_position_key = DataKey::AssignedPosition(reviewer.clone(), last_hash);
+        e.storage().persistent().set(&last_position_key, &position);
+        e.storage()
+            .persistent()
+            .extend_ttl(&last_position_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    e.storage().persistent().remove(&DataKey::AssignedProject(reviewer.clone(), last));
+    e.storage().persistent().remove(&position_key);
+    write_number_of_assigned_projects(e, reviewer, last);
+}
+
+// adds the project to the queue of each of its assignees
+pub fn enqueue(e: &Env, project_hash: &BytesN<32>) {
+    for reviewer in get_assignees(e, project_hash).iter() {
+        push_to_queue(e, &reviewer, project_hash);
+    }
+}
+
+// removes a decided project from the queue of each of its assignees, the
+// assignment itself is kept for the record
+pub fn dequeue(e: &Env, project_hash: &BytesN<32>) {
+    for reviewer in get_assignees(e, project_hash).iter() {
+        remove_from_queue(e, &reviewer, project_hash);
+    }
+}
+
+pub fn assign(e: &Env, project_hash: &BytesN<32>, reviewers: &Vec<Address>) -> Result<(), ProjectsError> {
+    // projects migrated from the instance storage have no known submitter
+    let submitter = project::get_project_record(e, project_hash).map(|record| record.submitter);
+    let mut assignees = Vec::new(e);
+    for reviewer in reviewers.iter() {
+        if !role::has_role(e, &Role::Reviewer, &reviewer) {
+            return Err(ProjectsError::NotWhitelisted);
+        }
+        if !category::can_review(e, project_hash, &reviewer) {
+            return Err(ProjectsError::NotCategoryReviewer);
+        }
+        if let Some(submitter) = &submitter {
+            if conflict::has_conflict(e, &reviewer, submitter) {
+                return Err(ProjectsError::ConflictOfInterest);
+            }
+        }
+        if assignees.contains(&reviewer) {
+            return Err(ProjectsError::InvalidAssignment);
+        }
+        assignees.push_back(reviewer);
+    }
+    // the previous assignees no longer see the project in their queue
+    dequeue(e, project_hash);
+    let key = DataKey::Assignees(project_hash.clone());
+    if assignees.is_empty() {
+        e.storage().persistent().remove(&key);
+        return Ok(());
+    }
+    e.storage().persistent().set(&key, &assignees);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    enqueue(e, project_hash);
+    Ok(())
+}
+
+pub fn read_auto_assign_count(e: &Env) -> u32 {
+    // projects are open to every reviewer by default
+    e.storage().instance().get(&DataKey::AutoAssignCount).unwrap_or(0)
+}
+
+pub fn write_auto_assign_count(e: &Env, count: u32) {
+    e.storage().instance().set(&DataKey::AutoAssignCount, &count);
+}
+
+// picks the next reviewers in the role members list, starting where the
+// previous project stopped so the work is spread evenly. Reviewers outside
+// the project category or with a conflict of interest with the submitter
+// are skipped
+pub fn next_reviewers(e: &Env, count: u32, project_hash: &BytesN<32>, submitter: &Address) -> Vec<Address> {
+    let mut reviewers = Vec::new(e);
+    let total = role::get_role_member_count(e, &Role::Reviewer);
+    if total == 0 {
+        return reviewers;
+    }
+    let cursor: u32 = e.storage().instance().get(&DataKey::AssignmentCursor).unwrap_or(0);
+    let mut scanned = 0;
+    while scanned < total && reviewers.len() < count {
+        let index = (cursor + scanned) % total;
+        scanned += 1;
+        if let Some(reviewer) = role::get_role_member(e, &Role::Reviewer, index) {
+            if category::can_review(e, project_hash, &reviewer) && !conflict::has_conflict(e, &reviewer, submitter) {
+                reviewers.push_back(reviewer);
+            }
+        }
+    }
+    e.storage().instance().set(&DataKey::AssignmentCursor, &((cursor + scanned) % total));
+    reviewers
+}
+
+pub fn get_assigned_projects(e: &Env, reviewer: &Address, cursor: u32, limit: u32) -> Vec<ProjectData> {
+    let count = get_number_of_assigned_projects(e, reviewer);
+    let end = count.min(cursor.saturating_add(limit));
+    let mut result = Vec::new(e);
+    for i in cursor..end {
+        let key = DataKey::AssignedProject(reviewer.clone(), i);
+        if let Some(project_hash) = e.storage().persistent().get::<_, BytesN<32>>(&key) {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+            let status = project::get_project_status(e, &project_hash);
+            result.push_back(ProjectData {
+                hash: project_hash,
+                status,
+            });
+        }
+    }
+    result
+}
@@ -0,0 +1,123 @@
+use soroban_sdk::{Address, BytesN, Env, Symbol, Vec};
+
+use crate::error::ProjectsError;
+use crate::project;
+use crate::storage_types::{DataKey, ProjectData};
+use crate::storage_types::{PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD};
+
+// the list lives in the instance storage, so it is kept short
+pub(crate) const MAX_CATEGORIES: u32 = 50;
+
+pub fn get_categories(e: &Env) -> Vec<Symbol> {
+    e.storage().instance().get(&DataKey::Categories).unwrap_or(Vec::new(e))
+}
+
+pub fn is_category(e: &Env, category: &Symbol) -> bool {
+    get_categories(e).contains(category)
+}
+
+pub fn add_category(e: &Env, category: &Symbol) -> Result<(), ProjectsError> {
+    let mut categories = get_categories(e);
+    if categories.contains(category) || categories.len() >= MAX_CATEGORIES {
+        return Err(ProjectsError::InvalidCategory);
+    }
+    categories.push_back(category.clone());
+    e.storage().instance().set(&DataKey::Categories, &categories);
+    Ok(())
+}
+
+// projects already in the category keep it, only new submissions are refused
+pub fn remove_category(e: &Env, category: &Symbol) -> Result<(), ProjectsError> {
+    let mut categories = get_categories(e);
+    let index = categories.first_index_of(category).ok_or(ProjectsError::UnknownCategory)?;
+    categories.remove(index);
+    e.storage().instance().set(&DataKey::Categories, &categories);
+    Ok(())
+}
+
+pub fn is_category_reviewer(e: &Env, category: &Symbol, reviewer: &Address) -> bool {
+    let key = DataKey::CategoryReviewer(category.clone(), reviewer.clone());
+    if e.storage().persistent().has(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        true
+    } else {
+        false
+    }
+}
+
+pub fn add_category_reviewer(e: &Env, category: &Symbol, reviewer: &Address) {
+    let key = DataKey::CategoryReviewer(category.clone(), reviewer.clone());
+    e.storage().persistent().set(&key, &true);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+pub fn remove_category_reviewer(e: &Env, category: &Symbol, reviewer: &Address) {
+    let key = DataKey::CategoryReviewer(category.clone(), reviewer.clone());
+    e.storage().persistent().remove(&key);
+}
+
+// projects migrated from the first layout or submitted before categories
+// existed have none
+pub fn get_project_category(e: &Env, project_hash: &BytesN<32>) -> Option<Symbol> {
+    let key = DataKey::ProjectCategory(project_hash.clone());
+    let category = e.storage().persistent().get::<_, Symbol>(&key);
+    if category.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    category
+}
+
+// a reviewer can review a project if it is whitelisted for its category
+pub fn can_review(e: &Env, project_hash: &BytesN<32>, reviewer: &Address) -> bool {
+    match get_project_category(e, project_hash) {
+        Some(category) => is_category_reviewer(e, &category, reviewer),
+        None => true,
+    }
+}
+
+pub fn get_number_of_projects_by_category(e: &Env, category: &Symbol) -> u32 {
+    e.storage().instance().get(&DataKey::CategoryProjectCount(category.clone())).unwrap_or(0)
+}
+
+pub fn set_project_category(e: &Env, project_hash: &BytesN<32>, category: &Symbol) {
+    let key = DataKey::ProjectCategory(project_hash.clone());
+    e.storage().persistent().set(&key, category);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+
+    // add the project to the index of its category
+    let count = get_number_of_projects_by_category(e, category);
+    let index_key = DataKey::CategoryProject(category.clone(), count);
+    e.storage().persistent().set(&index_key, project_hash);
+    e.storage()
+        .persistent()
+        .extend_ttl(&index_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    e.storage().instance().set(&DataKey::CategoryProjectCount(category.clone()), &(count + 1));
+}
+
+pub fn get_projects_by_category(e: &Env, category: &Symbol, cursor: u32, limit: u32) -> Vec<ProjectData> {
+    let count = get_number_of_projects_by_category(e, category);
+    let end = count.min(cursor.saturating_add(limit));
+    let mut result = Vec::new(e);
+    for i in cursor..end {
+        let key = DataKey::CategoryProject(category.clone(), i);
+        if let Some(project_hash) = e.storage().persistent().get::<_, BytesN<32>>(&key) {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+            let status = project::get_project_status(e, &project_hash);
+            result.push_back(ProjectData {
+                hash: project_hash,
+                status,
+            });
+        }
+    }
+    result
+}
@@ -0,0 +1,34 @@
+use soroban_sdk::{Address, Env};
+
+use crate::storage_types::DataKey;
+use crate::storage_types::{PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD};
+
+fn is_declared(e: &Env, reviewer: &Address, submitter: &Address) -> bool {
+    let key = DataKey::Conflict(reviewer.clone(), submitter.clone());
+    if e.storage().persistent().has(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        true
+    } else {
+        false
+    }
+}
+
+// a reviewer can not review its own projects nor the ones of a declared affiliation
+pub fn has_conflict(e: &Env, reviewer: &Address, submitter: &Address) -> bool {
+    reviewer == submitter || is_declared(e, reviewer, submitter)
+}
+
+pub fn declare_conflict(e: &Env, reviewer: &Address, submitter: &Address) {
+    let key = DataKey::Conflict(reviewer.clone(), submitter.clone());
+    e.storage().persistent().set(&key, &true);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+pub fn remove_conflict(e: &Env, reviewer: &Address, submitter: &Address) {
+    let key = DataKey::Conflict(reviewer.clone(), submitter.clone());
+    e.storage().persistent().remove(&key);
+}
@@ -1,11 +1,28 @@
 //! This contract demonstrates a sample implementation of the Soroban token
 //! interface.
 use crate::admin::{read_administrator, write_administrator};
-use crate::whitelist;
+use crate::admin::{read_pending_administrator, remove_pending_administrator, write_pending_administrator};
+use crate::error::ProjectsError;
+use crate::role;
+use crate::migration;
+use crate::pause;
+use crate::page;
+use crate::upgrade;
 use crate::project;
-use crate::storage_types::{ProjectStatusEnum, ProjectData, TrufaScoreValues};
+use crate::history;
+use crate::version;
+use crate::metadata;
+use crate::category;
+use crate::fee;
+use crate::deposit;
+use crate::review;
+use crate::assignment;
+use crate::conflict;
+use crate::deadline;
+use crate::scoring;
+use crate::storage_types::{DepositConfig, Page, PendingAdmin, ProjectDeposit, ProjectMetadata, SubmissionFee, Rationale, RejectionReason, StatusChange, ProjectStatusEnum, ProjectData, ProjectRecord, Review, ReviewVote, Role, ReviewerScore, ScoreAggregation, ScoringWeights, TrufaScoreValues};
 use crate::storage_types::{INSTANCE_BUMP_AMOUNT, INSTANCE_LIFETIME_THRESHOLD};
-use soroban_sdk::{contract, contractimpl, Address, Env, Vec, BytesN};
+use soroban_sdk::{contract, contractimpl, Address, Env, Symbol, Vec, BytesN};
 
 
 #[contract]
@@ -15,7 +32,8 @@ pub struct Projects;
 impl Projects {
     pub fn __constructor(e: Env, admin: Address, whitelist_addresses: Vec<Address>) {
         write_administrator(&e, &admin);
-        
+        upgrade::write_schema_version(&e, upgrade::SCHEMA_VERSION);
+
         // extend the instance lifetime
         // extend the instance lifetime
         e.storage()
@@ -23,7 +41,7 @@ impl Projects {
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
         for address in whitelist_addresses {
-            whitelist::add_to_whitelist(&e, &address);
+            role::grant_role(&e, &Role::Reviewer, &address);
             //emit event
             e.events().publish(
                 ("whitelist", "added"),
@@ -39,199 +57,610 @@ impl Projects {
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        whitelist::is_whitelisted(&e, &address)
+        role::has_role(&e, &Role::Reviewer, &address)
     }
 
-    pub fn add_to_whitelist(e: Env, address: Address) {
-        // only admins can do this
-        let admin = read_administrator(&e);
-        admin.require_auth();
+    pub fn add_to_whitelist(e: Env, from: Address, address: Address) -> Result<(), ProjectsError> {
+        // only the managers of the reviewer role can do this
+        from.require_auth();
+        if !role::can_manage_role(&e, &Role::Reviewer, &from) {
+            return Err(ProjectsError::Unauthorized);
+        }
 
-        // extend the instance lifetime
-        e.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
 
-        whitelist::add_to_whitelist(&e, &address);
+        role::grant_role(&e, &Role::Reviewer, &address);
 
         //emit event
         e.events().publish(
             ("whitelist", "added"),
             (address,),
-        )
+        );
+        Ok(())
     }
 
-    pub fn remove_from_whitelist(e: Env, address: Address) {
-        // only admins can do this
-        let admin = read_administrator(&e);
-        admin.require_auth();
+    pub fn remove_from_whitelist(e: Env, from: Address, address: Address) -> Result<(), ProjectsError> {
+        // only the managers of the reviewer role can do this
+        from.require_auth();
+        if !role::can_manage_role(&e, &Role::Reviewer, &from) {
+            return Err(ProjectsError::Unauthorized);
+        }
 
-        // extend the instance lifetime
-        e.storage()
-            .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
 
-        whitelist::remove_from_whitelist(&e, &address);
+        // the pending projects of the reviewer must be reassigned first
+        if assignment::get_number_of_assigned_projects(&e, &address) > 0 {
+            return Err(ProjectsError::ReviewerStillAssigned);
+        }
+        role::revoke_role(&e, &Role::Reviewer, &address);
 
         // emit event
         e.events().publish(
             ("whitelist", "removed"),
             (address,),
         );
+        Ok(())
+    }
+
+    pub fn add_project(e: Env, from: Address, project_hash: BytesN<32>, category: Symbol, metadata: Option<ProjectMetadata>) -> Result<(), ProjectsError> {
+        // check authorization
+        from.require_auth();
+
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
+
+        // new projects can only be submitted in an existing category
+        if !category::is_category(&e, &category) {
+            return Err(ProjectsError::UnknownCategory);
+        }
+
+        submit_project(&e, &from, &project_hash, Some(&category), metadata.as_ref())
     }
 
-    pub fn add_project(e: Env, from: Address, project_hash: BytesN<32>) {
+    pub fn resubmit_project(e: Env, from: Address, previous_hash: BytesN<32>, new_hash: BytesN<32>, metadata: Option<ProjectMetadata>) -> Result<(), ProjectsError> {
         // check authorization
         from.require_auth();
 
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
+
+        // only the submitter can resubmit its project
+        let record = project::get_project_record(&e, &previous_hash).ok_or(ProjectsError::ProjectNotFound)?;
+        if record.submitter != from {
+            return Err(ProjectsError::NotProjectOwner);
+        }
+        // only the latest version can be resubmitted, once it is rejected or expired
+        if version::get_latest(&e, &previous_hash) != previous_hash {
+            return Err(ProjectsError::NotLatestVersion);
+        }
+        let status = project::get_project_status(&e, &previous_hash);
+        if status != ProjectStatusEnum::Rejected && status != ProjectStatusEnum::Expired {
+            return Err(ProjectsError::NotRejected);
+        }
+        // an expiry that was not swept yet is recorded so the old verdict is kept
+        if project::get_recorded_project_status(&e, &previous_hash) != status {
+            expire_project(&e, &previous_hash);
+        }
+
+        // the category of the previous version is carried over, and so is its
+        // metadata unless new one is given
+        let category = category::get_project_category(&e, &previous_hash);
+        let metadata = metadata.or_else(|| metadata::get_metadata(&e, &previous_hash));
+        submit_project(&e, &from, &new_hash, category.as_ref(), metadata.as_ref())?;
+        version::add_version(&e, &previous_hash, &new_hash);
+        // emit event
+        e.events().publish(
+            ("project", "resubmitted"),
+            (from, previous_hash, new_hash),
+        );
+        Ok(())
+    }
+
+    pub fn get_project_versions(e: Env, project_hash: BytesN<32>) -> Result<Vec<BytesN<32>>, ProjectsError> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        if project::get_project_status(&e, &project_hash) == ProjectStatusEnum::NotSet {
+            return Err(ProjectsError::ProjectNotFound);
+        }
+        Ok(version::get_versions(&e, &project_hash))
+    }
+
+    pub fn get_latest_version(e: Env, project_hash: BytesN<32>) -> Result<BytesN<32>, ProjectsError> {
+
         // extend the instance lifetime
         e.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        // check that project does not exists
+        if project::get_project_status(&e, &project_hash) == ProjectStatusEnum::NotSet {
+            return Err(ProjectsError::ProjectNotFound);
+        }
+        Ok(version::get_latest(&e, &project_hash))
+    }
+
+    pub fn cast_review(e: Env, from: Address, project_hash: BytesN<32>, vote: ReviewVote) -> Result<ProjectStatusEnum, ProjectsError> {
+        // check authorization
+        from.require_auth();
+
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
+
+        // check that this address is a reviewer
+        let ok = role::has_role(&e, &Role::Reviewer, &from);
+        if !ok {
+            return Err(ProjectsError::NotWhitelisted);
+        }
+        // check that project is pending
         let status = project::get_project_status(&e, &project_hash);
-        if status != ProjectStatusEnum::NotSet {
-            // throw an error
-            panic!("Project already exists");
+        if status != ProjectStatusEnum::Pending {
+            return Err(ProjectsError::NotPending);
+        }
+        // check that this reviewer is assigned to the project, if it has assignees
+        if !assignment::is_allowed(&e, &project_hash, &from) {
+            return Err(ProjectsError::NotAssigned);
+        }
+        // check that this reviewer is whitelisted for the project category
+        if !category::can_review(&e, &project_hash, &from) {
+            return Err(ProjectsError::NotCategoryReviewer);
+        }
+        // check that the reviewer is not the submitter nor affiliated to it
+        if let Some(record) = project::get_project_record(&e, &project_hash) {
+            if conflict::has_conflict(&e, &from, &record.submitter) {
+                return Err(ProjectsError::ConflictOfInterest);
+            }
         }
-        // add the project in Pending status
-        project::set_project_status(&e, &project_hash, &ProjectStatusEnum::Pending);
+        // check that the scores are in range and consistent
+        if let ReviewVote::Approve(trufa_score_values) = &vote {
+            scoring::validate_score(&e, trufa_score_values)?;
+        }
+        // store the vote of this reviewer
+        let reviews = review::add_review(&e, &project_hash, &from, &vote)?;
         // emit event
         e.events().publish(
-            ("project", "added"),
-            (from, project_hash.clone(), ProjectStatusEnum::Pending),
+            ("review", "cast"),
+            (from.clone(), project_hash.clone(), vote.clone()),
         );
+
+        // the project stays pending until one of the decisions reaches the threshold.
+        // The threshold may have been raised after the project was assigned, so it
+        // never asks for more votes than there are assignees
+        let assignees = assignment::get_assignees(&e, &project_hash);
+        let mut threshold = review::read_threshold(&e);
+        if !assignees.is_empty() {
+            threshold = threshold.min(assignees.len());
+        }
+        let (approvals, rejections) = review::count_votes(&reviews);
+        if approvals >= threshold {
+            // set the project status to approved
+            project::set_project_status(&e, &project_hash, &ProjectStatusEnum::Approved, Some(&from), None);
+            assignment::dequeue(&e, &project_hash);
+
+            // set the project trufa score values aggregated from every approving reviewer
+            let scores = scoring::reviewer_scores(&e, &reviews);
+            let aggregation = scoring::read_aggregation(&e);
+            let weights_version = scoring::read_weights_version(&e);
+            let weights = scoring::read_current_weights(&e);
+            let trufa_score_values = scoring::aggregate_scores(&e, &scores, &aggregation, &weights);
+            project::set_trufa_score(&e, &project_hash, &trufa_score_values);
+            scoring::set_score_weights_version(&e, &project_hash, weights_version);
+            // the whole deposit goes back to the submitter
+            settle_deposit(&e, &project_hash, 0);
+
+            // emit event
+            e.events().publish(
+                ("project", "approved"),
+                (from, project_hash.clone(), ProjectStatusEnum::Approved),
+            );
+            return Ok(ProjectStatusEnum::Approved);
+        }
+        // only a reject vote can reach the rejection threshold
+        if let ReviewVote::Reject(rationale) = &vote {
+            if rejections >= threshold {
+                // set the project status to rejected, with the rationale of the deciding vote
+                project::set_project_status(&e, &project_hash, &ProjectStatusEnum::Rejected, Some(&from), rationale.document.as_ref());
+                review::set_rejection_rationale(&e, &project_hash, rationale);
+                assignment::dequeue(&e, &project_hash);
+                // part of the deposit is forfeited to the treasury
+                settle_deposit(&e, &project_hash, deposit::read_config(&e).forfeit_bps);
+                // emit event
+                e.events().publish(
+                    ("project", "rejected"),
+                    (from, project_hash.clone(), ProjectStatusEnum::Rejected, rationale.clone()),
+                );
+                return Ok(ProjectStatusEnum::Rejected);
+            }
+        }
+        Ok(ProjectStatusEnum::Pending)
     }
 
-    pub fn set_project_approved(e: Env, from: Address, project_hash: BytesN<32>, trufa_score_values: TrufaScoreValues) {
-        // check authorization
-        from.require_auth();
+    pub fn set_project_approved(e: Env, from: Address, project_hash: BytesN<32>, trufa_score_values: TrufaScoreValues) -> Result<(), ProjectsError> {
+        // an approve vote, the project is approved once the threshold is reached
+        Self::cast_review(e, from, project_hash, ReviewVote::Approve(trufa_score_values))?;
+        Ok(())
+    }
+
+    pub fn set_project_rejected(e: Env, from: Address, project_hash: BytesN<32>, reason: RejectionReason, document: Option<BytesN<32>>) -> Result<(), ProjectsError> {
+        // a reject vote, the project is rejected once the threshold is reached
+        Self::cast_review(e, from, project_hash, ReviewVote::Reject(Rationale { reason, document }))?;
+        Ok(())
+    }
+
+    pub fn get_rejection_rationale(e: Env, project_hash: BytesN<32>) -> Option<Rationale> {
 
         // extend the instance lifetime
         e.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        // check that this address is whitelisted
-        let ok = whitelist::is_whitelisted(&e, &from);
-        if !ok {
-            // throw an error
-            panic!("Address is not whitelisted");
+        review::get_rejection_rationale(&e, &project_hash)
+    }
+
+    pub fn get_reset_rationale(e: Env, project_hash: BytesN<32>) -> Option<Rationale> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        review::get_reset_rationale(&e, &project_hash)
+    }
+
+    pub fn get_reviews(e: Env, project_hash: BytesN<32>) -> Vec<Review> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        review::get_reviews(&e, &project_hash)
+    }
+
+    pub fn set_review_threshold(e: Env, threshold: u32) -> Result<(), ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        // automatic assignment must still pick enough reviewers to decide
+        let auto_assign_count = assignment::read_auto_assign_count(&e);
+        if auto_assign_count > 0 && auto_assign_count < threshold {
+            return Err(ProjectsError::InvalidThreshold);
         }
-        // check that project is pending
+        review::write_threshold(&e, threshold)?;
+        // emit event
+        e.events().publish(
+            ("review", "threshold"),
+            (threshold,),
+        );
+        Ok(())
+    }
+
+    pub fn get_review_threshold(e: Env) -> u32 {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        review::read_threshold(&e)
+    }
+
+    pub fn assign_reviewers(e: Env, project_hash: BytesN<32>, reviewers: Vec<Address>) -> Result<(), ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
+
+        // only pending projects can be assigned
         let status = project::get_project_status(&e, &project_hash);
-        if status != ProjectStatusEnum::Pending{
-            // throw an error
-            panic!("Project is not pending");
+        if status != ProjectStatusEnum::Pending {
+            return Err(ProjectsError::NotPending);
+        }
+        // an empty list opens the project to every reviewer again, otherwise
+        // there must be enough assignees to reach the review threshold
+        if !reviewers.is_empty() && reviewers.len() < review::read_threshold(&e) {
+            return Err(ProjectsError::InvalidAssignment);
+        }
+        assignment::assign(&e, &project_hash, &reviewers)?;
+        // emit event
+        e.events().publish(
+            ("project", "assigned"),
+            (project_hash, reviewers),
+        );
+        Ok(())
+    }
+
+    pub fn get_assigned_reviewers(e: Env, project_hash: BytesN<32>) -> Vec<Address> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        assignment::get_assignees(&e, &project_hash)
+    }
+
+    pub fn get_assigned_projects(e: Env, reviewer: Address, cursor: u32, limit: u32) -> Vec<ProjectData> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        assignment::get_assigned_projects(&e, &reviewer, cursor, page::clamp_limit(&e, limit))
+    }
+
+    pub fn get_number_of_assigned_projects(e: Env, reviewer: Address) -> u32 {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        assignment::get_number_of_assigned_projects(&e, &reviewer)
+    }
+
+    pub fn set_auto_assign_count(e: Env, count: u32) -> Result<(), ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        // like assign_reviewers, there must be enough assignees to reach the review threshold
+        if count > 0 && count < review::read_threshold(&e) {
+            return Err(ProjectsError::InvalidAssignment);
         }
-        // set the project status to approved
-        project::set_project_status(&e, &project_hash, &ProjectStatusEnum::Approved);
+        assignment::write_auto_assign_count(&e, count);
+        // emit event
+        e.events().publish(
+            ("review", "auto_assign"),
+            (count,),
+        );
+        Ok(())
+    }
+
+    pub fn get_auto_assign_count(e: Env) -> u32 {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        assignment::read_auto_assign_count(&e)
+    }
+
+    pub fn declare_conflict(e: Env, reviewer: Address, submitter: Address) -> Result<(), ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
 
-        // set the project trufa score values
-        project::set_trufa_score(&e, &project_hash, &trufa_score_values);
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
 
+        conflict::declare_conflict(&e, &reviewer, &submitter);
         // emit event
         e.events().publish(
-            ("project", "approved"),
-            (from, project_hash.clone(), ProjectStatusEnum::Approved),
+            ("conflict", "declared"),
+            (reviewer, submitter),
         );
+        Ok(())
     }
 
-    pub fn set_project_rejected(e: Env, from: Address, project_hash: BytesN<32>) {
-        // get the current addess that executed this function
-        from.require_auth();
+    pub fn remove_conflict(e: Env, reviewer: Address, submitter: Address) -> Result<(), ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
+
+        conflict::remove_conflict(&e, &reviewer, &submitter);
+        // emit event
+        e.events().publish(
+            ("conflict", "removed"),
+            (reviewer, submitter),
+        );
+        Ok(())
+    }
+
+    pub fn has_conflict(e: Env, reviewer: Address, submitter: Address) -> bool {
 
         // extend the instance lifetime
         e.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        // check that this address is whitelisted
-        let ok = whitelist::is_whitelisted(&e, &from);
-        if !ok {
-            // throw an error
-            panic!("Address is not whitelisted");
+        conflict::has_conflict(&e, &reviewer, &submitter)
+    }
+
+    pub fn update_project_metadata(e: Env, from: Address, project_hash: BytesN<32>, metadata: ProjectMetadata) -> Result<(), ProjectsError> {
+        // check authorization
+        from.require_auth();
+
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
+
+        // only the submitter can update its project
+        let record = project::get_project_record(&e, &project_hash).ok_or(ProjectsError::ProjectNotFound)?;
+        if record.submitter != from {
+            return Err(ProjectsError::NotProjectOwner);
         }
-        // check that project is pending
+        // the metadata is frozen once the project is decided
         let status = project::get_project_status(&e, &project_hash);
         if status != ProjectStatusEnum::Pending {
-            // throw an error
-            panic!("Project is not pending");
+            return Err(ProjectsError::NotPending);
         }
-        // set the project status to rejected
-        project::set_project_status(&e, &project_hash, &ProjectStatusEnum::Rejected);
+        metadata::validate_metadata(&metadata)?;
+        // the category is fixed at submission
+        if let Some(category) = category::get_project_category(&e, &project_hash) {
+            if metadata.category != category {
+                return Err(ProjectsError::InvalidMetadata);
+            }
+        }
+        metadata::set_metadata(&e, &project_hash, &metadata);
         // emit event
         e.events().publish(
-            ("project", "rejected"),
-            (from, project_hash.clone(), ProjectStatusEnum::Rejected),
+            ("project", "metadata"),
+            (from, project_hash, metadata),
         );
+        Ok(())
+    }
+
+    pub fn get_project_metadata(e: Env, project_hash: BytesN<32>) -> Option<ProjectMetadata> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        metadata::get_metadata(&e, &project_hash)
     }
 
-    pub fn reset_project(e: Env, project_hash: BytesN<32>) {
+    pub fn add_category(e: Env, category: Symbol) -> Result<(), ProjectsError> {
         // only admin can do this
-        let admin = read_administrator(&e);
+        let admin = read_administrator(&e)?;
         admin.require_auth();
 
-        // only Rejected projects can be reset
-        let status = project::get_project_status(&e, &project_hash);
-        if status != ProjectStatusEnum::Rejected {
-            // throw an error
-            panic!("Project is not rejected");
-        }
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
+
+        category::add_category(&e, &category)?;
+        // emit event
+        e.events().publish(
+            ("category", "added"),
+            (category,),
+        );
+        Ok(())
+    }
+
+    pub fn remove_category(e: Env, category: Symbol) -> Result<(), ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
+
+        category::remove_category(&e, &category)?;
+        // emit event
+        e.events().publish(
+            ("category", "removed"),
+            (category,),
+        );
+        Ok(())
+    }
+
+    pub fn get_categories(e: Env) -> Vec<Symbol> {
 
         // extend the instance lifetime
         e.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        // set project status to Pending
-        project::set_project_status(&e, &project_hash, &ProjectStatusEnum::Pending);
+        category::get_categories(&e)
     }
 
-    pub fn get_project_status(e: Env, project_hash: BytesN<32>) -> ProjectStatusEnum {
+    pub fn add_category_reviewer(e: Env, from: Address, category: Symbol, reviewer: Address) -> Result<(), ProjectsError> {
+        // only the managers of the reviewer role can do this
+        from.require_auth();
+        if !role::can_manage_role(&e, &Role::Reviewer, &from) {
+            return Err(ProjectsError::Unauthorized);
+        }
+
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
+
+        if !category::is_category(&e, &category) {
+            return Err(ProjectsError::UnknownCategory);
+        }
+        category::add_category_reviewer(&e, &category, &reviewer);
+        // emit event
+        e.events().publish(
+            ("category", "reviewer_added"),
+            (category, reviewer),
+        );
+        Ok(())
+    }
+
+    pub fn remove_category_reviewer(e: Env, from: Address, category: Symbol, reviewer: Address) -> Result<(), ProjectsError> {
+        // only the managers of the reviewer role can do this
+        from.require_auth();
+        if !role::can_manage_role(&e, &Role::Reviewer, &from) {
+            return Err(ProjectsError::Unauthorized);
+        }
+
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
+
+        // reviewers of a removed category can still be removed
+        category::remove_category_reviewer(&e, &category, &reviewer);
+        // emit event
+        e.events().publish(
+            ("category", "reviewer_removed"),
+            (category, reviewer),
+        );
+        Ok(())
+    }
+
+    pub fn is_category_reviewer(e: Env, category: Symbol, reviewer: Address) -> bool {
 
         // extend the instance lifetime
         e.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        project::get_project_status(&e, &project_hash)
+        category::is_category_reviewer(&e, &category, &reviewer)
     }
 
-    pub fn get_projects_statuses_in_bulk(e: Env, start: u32, end: u32) -> Vec<ProjectData> {
+    pub fn get_project_category(e: Env, project_hash: BytesN<32>) -> Option<Symbol> {
 
         // extend the instance lifetime
         e.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        project::get_projects_statuses_in_bulk(&e, start, end)
+        category::get_project_category(&e, &project_hash)
     }
 
-    pub fn get_projects_statuses_from_vec(e: Env, project_hashes: Vec<BytesN<32>>) -> Vec<ProjectStatusEnum> {
+    pub fn get_projects_by_category(e: Env, category: Symbol, cursor: u32, limit: u32) -> Vec<ProjectData> {
 
         // extend the instance lifetime
         e.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        project::get_projects_statuses_from_vec(&e, project_hashes)
+        category::get_projects_by_category(&e, &category, cursor, page::clamp_limit(&e, limit))
     }
 
-    pub fn get_all_projects_statuses(e: Env) -> Vec<ProjectData> {
+    pub fn count_by_category(e: Env, category: Symbol) -> u32 {
 
         // extend the instance lifetime
         e.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        let end = project::get_number_of_projects(&e);
-        project::get_projects_statuses_in_bulk(&e, 0, end)
+        category::get_number_of_projects_by_category(&e, &category)
     }
 
-    pub fn set_admin(e: Env, new_admin: Address) {
-        let admin = read_administrator(&e);
+    pub fn set_submission_fee(e: Env, token: Address, amount: i128) -> Result<(), ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
         admin.require_auth();
 
         // extend the instance lifetime
@@ -239,26 +668,990 @@ impl Projects {
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        write_administrator(&e, &new_admin);
-        // add event
+        fee::write_fee(&e, &SubmissionFee { token: token.clone(), amount })?;
+        // emit event
         e.events().publish(
-            ("admin", "updated"),
-            (admin, new_admin),
+            ("fee", "updated"),
+            (token, amount),
         );
+        Ok(())
     }
 
-    pub fn get_trufa_score(e: Env, project_hash: BytesN<32>) -> TrufaScoreValues {
-        // check that project was approved
-        let status = project::get_project_status(&e, &project_hash);
-        if status != ProjectStatusEnum::Approved {
-            // throw an error
-            panic!("Project is not approved");
-        }
+    pub fn get_submission_fee(e: Env) -> Option<SubmissionFee> {
+
         // extend the instance lifetime
         e.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
 
-        project::get_trufa_score(&e, &project_hash)
+        fee::read_fee(&e)
+    }
+
+    pub fn get_treasury_balance(e: Env) -> i128 {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        fee::read_treasury(&e)
+    }
+
+    pub fn withdraw_fees(e: Env, to: Address, amount: i128) -> Result<(), ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
+
+        fee::withdraw(&e, &to, amount)?;
+        // emit event
+        e.events().publish(
+            ("fee", "withdrawn"),
+            (to, amount),
+        );
+        Ok(())
+    }
+
+    pub fn set_review_deposit(e: Env, amount: i128, forfeit_bps: u32) -> Result<(), ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        deposit::write_config(&e, &DepositConfig { amount, forfeit_bps })?;
+        // emit event
+        e.events().publish(
+            ("deposit", "updated"),
+            (amount, forfeit_bps),
+        );
+        Ok(())
+    }
+
+    pub fn get_review_deposit(e: Env) -> DepositConfig {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        deposit::read_config(&e)
+    }
+
+    pub fn get_project_deposit(e: Env, project_hash: BytesN<32>) -> Option<ProjectDeposit> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        deposit::get_deposit(&e, &project_hash)
+    }
+
+    pub fn get_deposits_held(e: Env) -> i128 {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        deposit::read_held(&e)
+    }
+
+    pub fn withdraw_project(e: Env, from: Address, project_hash: BytesN<32>) -> Result<(), ProjectsError> {
+        // check authorization
+        from.require_auth();
+
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
+
+        // only the submitter can withdraw its project
+        let record = project::get_project_record(&e, &project_hash).ok_or(ProjectsError::ProjectNotFound)?;
+        if record.submitter != from {
+            return Err(ProjectsError::NotProjectOwner);
+        }
+        // check that project is pending
+        let status = project::get_project_status(&e, &project_hash);
+        if status != ProjectStatusEnum::Pending {
+            return Err(ProjectsError::NotPending);
+        }
+        // set the project status to withdrawn
+        project::set_project_status(&e, &project_hash, &ProjectStatusEnum::Withdrawn, Some(&from), None);
+        assignment::dequeue(&e, &project_hash);
+        settle_deposit(&e, &project_hash, 0);
+        // emit event
+        e.events().publish(
+            ("project", "withdrawn"),
+            (from, project_hash.clone(), ProjectStatusEnum::Withdrawn),
+        );
+        Ok(())
+    }
+
+    pub fn reset_project(e: Env, from: Address, project_hash: BytesN<32>, reason: RejectionReason, document: Option<BytesN<32>>) -> Result<(), ProjectsError> {
+        // only score auditors and the super admin can do this
+        from.require_auth();
+        if !role::has_role(&e, &Role::ScoreAuditor, &from) && !role::has_role(&e, &Role::SuperAdmin, &from) {
+            return Err(ProjectsError::Unauthorized);
+        }
+
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
+
+        // only Rejected and Expired projects can be reset
+        let status = project::get_project_status(&e, &project_hash);
+        if status != ProjectStatusEnum::Rejected && status != ProjectStatusEnum::Expired {
+            return Err(ProjectsError::NotRejected);
+        }
+        // an expiry that was not swept yet is recorded first, so the history
+        // and the status index show it before the new review round
+        if project::get_recorded_project_status(&e, &project_hash) != status {
+            expire_project(&e, &project_hash);
+        }
+
+        // set project status to Pending and start a new review round
+        project::set_project_status(&e, &project_hash, &ProjectStatusEnum::Pending, Some(&from), document.as_ref());
+        review::clear_reviews(&e, &project_hash);
+        let rationale = Rationale { reason, document };
+        review::set_reset_rationale(&e, &project_hash, &rationale);
+        deadline::start_review_window(&e, &project_hash);
+        // the assignees get the project back in their queue
+        assignment::enqueue(&e, &project_hash);
+        // emit event
+        e.events().publish(
+            ("project", "reset"),
+            (from, project_hash, ProjectStatusEnum::Pending, rationale),
+        );
+        Ok(())
+    }
+
+    pub fn expire_projects(e: Env, cursor: u32, limit: u32) -> Result<Option<u32>, ProjectsError> {
+        // anyone can do this, only overdue projects are changed
+
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
+
+        // walk a page of the pending index, where overdue projects are reported as expired.
+        // An expired project leaves the index and the last pending project takes its
+        // slot, so that slot is read again instead of moving forward
+        let limit = page::clamp_limit(&e, limit);
+        let mut next_cursor = cursor;
+        for _ in 0..limit {
+            let project_data = match project::get_projects_by_status(&e, &ProjectStatusEnum::Pending, next_cursor, 1).first() {
+                Some(project_data) => project_data,
+                None => break,
+            };
+            if project_data.status != ProjectStatusEnum::Expired {
+                next_cursor += 1;
+                continue;
+            }
+            expire_project(&e, &project_data.hash);
+        }
+        // the sweep is over once the end of the pending index is reached. Like
+        // list_projects, an empty page has no next cursor
+        if limit > 0 && next_cursor < project::get_count_by_status(&e, &ProjectStatusEnum::Pending) {
+            Ok(Some(next_cursor))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn set_review_window(e: Env, ledgers: u32) -> Result<(), ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        deadline::write_review_window(&e, ledgers);
+        // emit event
+        e.events().publish(
+            ("review", "window"),
+            (ledgers,),
+        );
+        Ok(())
+    }
+
+    pub fn get_review_window(e: Env) -> u32 {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        deadline::read_review_window(&e)
+    }
+
+    pub fn get_review_deadline(e: Env, project_hash: BytesN<32>) -> Option<u32> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        deadline::get_deadline(&e, &project_hash)
+    }
+
+    pub fn get_project_status(e: Env, project_hash: BytesN<32>) -> ProjectStatusEnum {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        project::get_project_status(&e, &project_hash)
+    }
+
+    pub fn get_projects_statuses_in_bulk(e: Env, start: u32, end: u32) -> Vec<ProjectData> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        // ranges longer than the maximum page size are cut
+        let end = end.min(start.saturating_add(page::read_max_page_size(&e)));
+        project::get_projects_statuses_in_bulk(&e, start, end)
+    }
+
+    pub fn get_projects_statuses_from_vec(e: Env, project_hashes: Vec<BytesN<32>>) -> Result<Vec<ProjectStatusEnum>, ProjectsError> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        if project_hashes.len() > page::read_max_page_size(&e) {
+            return Err(ProjectsError::PageTooLarge);
+        }
+        Ok(project::get_projects_statuses_from_vec(&e, project_hashes))
+    }
+
+    // deprecated, use list_projects. Fails once there are more projects than
+    // the maximum page size
+    pub fn get_all_projects_statuses(e: Env) -> Result<Vec<ProjectData>, ProjectsError> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let end = project::get_number_of_projects(&e);
+        if end > page::read_max_page_size(&e) {
+            return Err(ProjectsError::PageTooLarge);
+        }
+        Ok(project::get_projects_statuses_in_bulk(&e, 0, end))
+    }
+
+    pub fn list_projects(e: Env, cursor: Option<u32>, limit: u32) -> Page {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let limit = page::clamp_limit(&e, limit);
+        project::list_projects(&e, cursor.unwrap_or(0), limit)
+    }
+
+    pub fn set_max_page_size(e: Env, max_page_size: u32) -> Result<(), ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        page::write_max_page_size(&e, max_page_size)?;
+        // emit event
+        e.events().publish(
+            ("page", "max_size"),
+            (max_page_size,),
+        );
+        Ok(())
+    }
+
+    pub fn get_max_page_size(e: Env) -> u32 {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        page::read_max_page_size(&e)
+    }
+
+    pub fn get_project_history(e: Env, project_hash: BytesN<32>, start: u32, limit: u32) -> Vec<StatusChange> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        history::get_history(&e, &project_hash, start, page::clamp_limit(&e, limit))
+    }
+
+    pub fn get_project_history_length(e: Env, project_hash: BytesN<32>) -> u32 {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        history::get_history_length(&e, &project_hash)
+    }
+
+    pub fn get_project_record(e: Env, project_hash: BytesN<32>) -> Result<ProjectRecord, ProjectsError> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        project::get_project_record(&e, &project_hash).ok_or(ProjectsError::ProjectNotFound)
+    }
+
+    pub fn get_project_owner(e: Env, project_hash: BytesN<32>) -> Result<Address, ProjectsError> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let record = project::get_project_record(&e, &project_hash).ok_or(ProjectsError::ProjectNotFound)?;
+        Ok(record.submitter)
+    }
+
+    pub fn get_projects_by_owner(e: Env, owner: Address, start: u32, limit: u32) -> Vec<ProjectData> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        project::get_projects_by_owner(&e, &owner, start, page::clamp_limit(&e, limit))
+    }
+
+    pub fn get_number_of_projects_by_owner(e: Env, owner: Address) -> u32 {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        project::get_number_of_projects_by_owner(&e, &owner)
+    }
+
+    pub fn get_projects_by_status(e: Env, status: ProjectStatusEnum, cursor: u32, limit: u32) -> Vec<ProjectData> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        project::get_projects_by_status(&e, &status, cursor, page::clamp_limit(&e, limit))
+    }
+
+    pub fn count_by_status(e: Env, status: ProjectStatusEnum) -> u32 {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        project::get_count_by_status(&e, &status)
+    }
+
+    pub fn migrate(e: Env, batch_start: u32, batch_size: u32, legacy_reviewers: Vec<Address>) -> Result<u32, ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        // move the next batch of projects and whitelisted addresses to the persistent storage
+        let cursor = migration::migrate(&e, batch_start, batch_size, &legacy_reviewers)?;
+        if upgrade::read_schema_version(&e) < upgrade::SCHEMA_VERSION {
+            // emit event
+            e.events().publish(
+                ("migration", "progress"),
+                (cursor,),
+            );
+        } else {
+            // emit event
+            e.events().publish(
+                ("migration", "completed"),
+                (cursor,),
+            );
+        }
+        Ok(cursor)
+    }
+
+    pub fn is_migration_pending(e: Env) -> bool {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        migration::is_pending(&e)
+    }
+
+    pub fn get_migration_cursor(e: Env) -> u32 {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        migration::get_cursor(&e)
+    }
+
+    pub fn upgrade(e: Env, new_wasm_hash: BytesN<32>) -> Result<(), ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        // the stored layout must be the one of this code, so an upgrade can
+        // not skip a migration
+        let version = upgrade::read_schema_version(&e);
+        if version < upgrade::SCHEMA_VERSION {
+            return Err(ProjectsError::MigrationInProgress);
+        }
+        upgrade::update_wasm(&e, &new_wasm_hash);
+        // emit event
+        e.events().publish(
+            ("contract", "upgraded"),
+            (new_wasm_hash, version, upgrade::SCHEMA_VERSION),
+        );
+        Ok(())
+    }
+
+    pub fn version(e: Env) -> u32 {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        upgrade::read_schema_version(&e)
+    }
+
+    pub fn get_reviewer_scores(e: Env, project_hash: BytesN<32>) -> Vec<ReviewerScore> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let reviews = review::get_reviews(&e, &project_hash);
+        scoring::reviewer_scores(&e, &reviews)
+    }
+
+    pub fn set_score_aggregation(e: Env, aggregation: ScoreAggregation) -> Result<(), ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        scoring::write_aggregation(&e, &aggregation);
+        // emit event
+        e.events().publish(
+            ("score", "aggregation"),
+            (aggregation,),
+        );
+        Ok(())
+    }
+
+    pub fn get_score_aggregation(e: Env) -> ScoreAggregation {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        scoring::read_aggregation(&e)
+    }
+
+    pub fn set_score_tolerance(e: Env, tolerance: u32) -> Result<(), ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        scoring::write_tolerance(&e, tolerance)?;
+        // emit event
+        e.events().publish(
+            ("score", "tolerance"),
+            (tolerance,),
+        );
+        Ok(())
+    }
+
+    pub fn get_score_tolerance(e: Env) -> u32 {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        scoring::read_tolerance(&e)
+    }
+
+    pub fn set_scoring_weights(e: Env, weights: ScoringWeights) -> Result<u32, ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let version = scoring::write_weights(&e, &weights)?;
+        // emit event
+        e.events().publish(
+            ("weights", "updated"),
+            (version, weights),
+        );
+        Ok(version)
+    }
+
+    pub fn get_scoring_weights(e: Env) -> ScoringWeights {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        scoring::read_current_weights(&e)
+    }
+
+    pub fn get_scoring_weights_version(e: Env) -> u32 {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        scoring::read_weights_version(&e)
+    }
+
+    pub fn get_scoring_weights_by_version(e: Env, version: u32) -> Result<ScoringWeights, ProjectsError> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        scoring::read_weights(&e, version).ok_or(ProjectsError::UnknownWeightsVersion)
+    }
+
+    pub fn get_score_weights_version(e: Env, project_hash: BytesN<32>) -> Result<u32, ProjectsError> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        scoring::get_score_weights_version(&e, &project_hash).ok_or(ProjectsError::NotApproved)
+    }
+
+    pub fn propose_admin(e: Env, new_admin: Address, expires_in_ledgers: Option<u32>) -> Result<(), ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        // the new admin only takes over after accepting, a new proposal replaces the previous one
+        let pending = PendingAdmin {
+            address: new_admin,
+            expiration_ledger: expires_in_ledgers.map(|ledgers| e.ledger().sequence().saturating_add(ledgers)),
+        };
+        write_pending_administrator(&e, &pending);
+        // emit event
+        e.events().publish(
+            ("admin", "proposed"),
+            (admin, pending.address, pending.expiration_ledger),
+        );
+        Ok(())
+    }
+
+    pub fn accept_admin(e: Env) -> Result<(), ProjectsError> {
+        let admin = read_administrator(&e)?;
+        let pending = read_pending_administrator(&e).ok_or(ProjectsError::NoPendingAdmin)?;
+        // only the proposed admin can accept
+        pending.address.require_auth();
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        if let Some(expiration_ledger) = pending.expiration_ledger {
+            if e.ledger().sequence() > expiration_ledger {
+                return Err(ProjectsError::AdminProposalExpired);
+            }
+        }
+        write_administrator(&e, &pending.address);
+        remove_pending_administrator(&e);
+        // emit event
+        e.events().publish(
+            ("admin", "accepted"),
+            (admin, pending.address),
+        );
+        Ok(())
+    }
+
+    pub fn cancel_admin_proposal(e: Env) -> Result<(), ProjectsError> {
+        // only admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let pending = read_pending_administrator(&e).ok_or(ProjectsError::NoPendingAdmin)?;
+        remove_pending_administrator(&e);
+        // emit event
+        e.events().publish(
+            ("admin", "cancelled"),
+            (admin, pending.address),
+        );
+        Ok(())
+    }
+
+    pub fn grant_role(e: Env, from: Address, role: Role, account: Address) -> Result<(), ProjectsError> {
+        // only the holders of the role admin can do this
+        from.require_auth();
+
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
+
+        // the super admin is changed through the admin transfer
+        if role == Role::SuperAdmin {
+            return Err(ProjectsError::InvalidRole);
+        }
+        if !role::can_manage_role(&e, &role, &from) {
+            return Err(ProjectsError::Unauthorized);
+        }
+
+        if role::grant_role(&e, &role, &account) {
+            // emit event
+            e.events().publish(
+                ("role", "granted"),
+                (role, account, from),
+            );
+        }
+        Ok(())
+    }
+
+    pub fn revoke_role(e: Env, from: Address, role: Role, account: Address) -> Result<(), ProjectsError> {
+        // only the holders of the role admin can do this
+        from.require_auth();
+
+        // extend the instance lifetime, writes are refused during a migration or a pause
+        ensure_writable(&e)?;
+
+        if role == Role::SuperAdmin {
+            return Err(ProjectsError::InvalidRole);
+        }
+        if !role::can_manage_role(&e, &role, &from) {
+            return Err(ProjectsError::Unauthorized);
+        }
+        // like remove_from_whitelist, the pending projects of a reviewer must be reassigned first
+        if role == Role::Reviewer && assignment::get_number_of_assigned_projects(&e, &account) > 0 {
+            return Err(ProjectsError::ReviewerStillAssigned);
+        }
+
+        if role::revoke_role(&e, &role, &account) {
+            // emit event
+            e.events().publish(
+                ("role", "revoked"),
+                (role, account, from),
+            );
+        }
+        Ok(())
+    }
+
+    pub fn set_role_admin(e: Env, role: Role, admin_role: Role) -> Result<(), ProjectsError> {
+        // only the super admin can do this
+        let admin = read_administrator(&e)?;
+        admin.require_auth();
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        // the super admin is not managed by any other role
+        if role == Role::SuperAdmin {
+            return Err(ProjectsError::InvalidRole);
+        }
+        role::set_role_admin(&e, &role, &admin_role);
+
+        // emit event
+        e.events().publish(
+            ("role", "admin_changed"),
+            (role, admin_role),
+        );
+        Ok(())
+    }
+
+    pub fn get_role_admin(e: Env, role: Role) -> Role {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        role::get_role_admin(&e, &role)
+    }
+
+    pub fn has_role(e: Env, role: Role, account: Address) -> bool {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        role::has_role(&e, &role, &account)
+    }
+
+    pub fn get_role_members(e: Env, role: Role, start: u32, limit: u32) -> Vec<Address> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        role::get_role_members(&e, &role, start, page::clamp_limit(&e, limit))
+    }
+
+    pub fn get_role_member_count(e: Env, role: Role) -> u32 {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        role::get_role_member_count(&e, &role)
+    }
+
+    pub fn pause(e: Env, from: Address) -> Result<(), ProjectsError> {
+        // only pausers and the super admin can do this
+        from.require_auth();
+        if !role::has_role(&e, &Role::Pauser, &from) && !role::has_role(&e, &Role::SuperAdmin, &from) {
+            return Err(ProjectsError::Unauthorized);
+        }
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        pause::write_paused(&e, true);
+
+        // emit event
+        e.events().publish(
+            ("contract", "paused"),
+            (from,),
+        );
+        Ok(())
+    }
+
+    pub fn unpause(e: Env, from: Address) -> Result<(), ProjectsError> {
+        // only pausers and the super admin can do this
+        from.require_auth();
+        if !role::has_role(&e, &Role::Pauser, &from) && !role::has_role(&e, &Role::SuperAdmin, &from) {
+            return Err(ProjectsError::Unauthorized);
+        }
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        pause::write_paused(&e, false);
+
+        // emit event
+        e.events().publish(
+            ("contract", "unpaused"),
+            (from,),
+        );
+        Ok(())
+    }
+
+    pub fn paused(e: Env) -> bool {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        pause::is_paused(&e)
+    }
+
+    pub fn get_admin(e: Env) -> Result<Address, ProjectsError> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        read_administrator(&e)
+    }
+
+    pub fn get_pending_admin(e: Env) -> Option<PendingAdmin> {
+
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        read_pending_administrator(&e)
+    }
+
+    pub fn get_trufa_score(e: Env, project_hash: BytesN<32>) -> Result<TrufaScoreValues, ProjectsError> {
+        // check that project was approved
+        let status = project::get_project_status(&e, &project_hash);
+        if status != ProjectStatusEnum::Approved {
+            return Err(ProjectsError::NotApproved);
+        }
+        // extend the instance lifetime
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        project::get_trufa_score(&e, &project_hash).ok_or(ProjectsError::NotApproved)
+    }
+}
+
+// shared by every state-changing entry point
+fn ensure_writable(e: &Env) -> Result<(), ProjectsError> {
+    // extend the instance lifetime
+    e.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+    // writes are refused until the storage migration is completed
+    if migration::is_pending(e) {
+        return Err(ProjectsError::MigrationInProgress);
+    }
+    // writes are refused while the contract is paused
+    if pause::is_paused(e) {
+        return Err(ProjectsError::Paused);
+    }
+    Ok(())
+}
+
+// adds a new project in Pending status, shared by add_project and resubmit_project
+fn submit_project(e: &Env, from: &Address, project_hash: &BytesN<32>, category: Option<&Symbol>, metadata: Option<&ProjectMetadata>) -> Result<(), ProjectsError> {
+    // check that project does not exists
+    let status = project::get_project_status(e, project_hash);
+    if status != ProjectStatusEnum::NotSet {
+        return Err(ProjectsError::ProjectAlreadyExists);
+    }
+    if let Some(metadata) = metadata {
+        metadata::validate_metadata(metadata)?;
+        // the metadata must agree with the project category
+        if let Some(category) = category {
+            if metadata.category != *category {
+                return Err(ProjectsError::InvalidMetadata);
+            }
+        }
+    }
+    // the submitter pays the fee into the treasury and posts the deposit
+    fee::charge(e, from);
+    deposit::collect(e, from, project_hash);
+    // add the project in Pending status
+    project::set_project_status(e, project_hash, &ProjectStatusEnum::Pending, Some(from), None);
+    // keep track of who submitted it
+    project::set_project_record(e, project_hash, from);
+    if let Some(category) = category {
+        category::set_project_category(e, project_hash, category);
+    }
+    if let Some(metadata) = metadata {
+        metadata::set_metadata(e, project_hash, metadata);
+    }
+    // the review deadline starts now
+    deadline::start_review_window(e, project_hash);
+    // emit event
+    e.events().publish(
+        ("project", "added"),
+        (from.clone(), project_hash.clone(), ProjectStatusEnum::Pending),
+    );
+
+    // route the project to the next reviewers when automatic assignment is on
+    let auto_assign_count = assignment::read_auto_assign_count(e);
+    if auto_assign_count > 0 {
+        let reviewers = assignment::next_reviewers(e, auto_assign_count, project_hash, from);
+        // when the category and conflicts leave too few reviewers to reach the
+        // threshold, the project stays open to every allowed reviewer
+        if reviewers.len() >= review::read_threshold(e) {
+            assignment::assign(e, project_hash, &reviewers)?;
+            // emit event
+            e.events().publish(
+                ("project", "assigned"),
+                (project_hash.clone(), reviewers),
+            );
+        }
+    }
+    Ok(())
+}
+
+// records the expiry of an overdue project, nobody is behind it so it has no actor
+fn expire_project(e: &Env, project_hash: &BytesN<32>) {
+    // set the project status to expired
+    project::set_project_status(e, project_hash, &ProjectStatusEnum::Expired, None, None);
+    assignment::dequeue(e, project_hash);
+    // the submitter is not to blame for an expiry
+    settle_deposit(e, project_hash, 0);
+    // emit event
+    e.events().publish(
+        ("project", "expired"),
+        (project_hash.clone(), ProjectStatusEnum::Expired),
+    );
+}
+
+// releases the deposit of a decided project, if it has one
+fn settle_deposit(e: &Env, project_hash: &BytesN<32>, forfeit_bps: u32) {
+    if let Some((deposit, forfeited)) = deposit::settle(e, project_hash, forfeit_bps) {
+        // emit event
+        e.events().publish(
+            ("deposit", "settled"),
+            (project_hash.clone(), deposit.depositor, deposit.amount - forfeited, forfeited),
+        );
     }
 }
@@ -0,0 +1,47 @@
+use soroban_sdk::{BytesN, Env};
+
+use crate::storage_types::DataKey;
+use crate::storage_types::{PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD};
+
+pub fn read_review_window(e: &Env) -> u32 {
+    // pending projects never expire by default
+    e.storage().instance().get(&DataKey::ReviewWindow).unwrap_or(0)
+}
+
+pub fn write_review_window(e: &Env, ledgers: u32) {
+    e.storage().instance().set(&DataKey::ReviewWindow, &ledgers);
+}
+
+// called every time a project enters the pending status
+pub fn start_review_window(e: &Env, project_hash: &BytesN<32>) {
+    let key = DataKey::ReviewDeadline(project_hash.clone());
+    let window = read_review_window(e);
+    if window == 0 {
+        e.storage().persistent().remove(&key);
+        return;
+    }
+    let deadline = e.ledger().sequence().saturating_add(window);
+    e.storage().persistent().set(&key, &deadline);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+pub fn get_deadline(e: &Env, project_hash: &BytesN<32>) -> Option<u32> {
+    let key = DataKey::ReviewDeadline(project_hash.clone());
+    let deadline = e.storage().persistent().get::<_, u32>(&key);
+    if deadline.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    deadline
+}
+
+// the deadline is the last ledger in which the project can still be reviewed
+pub fn is_overdue(e: &Env, project_hash: &BytesN<32>) -> bool {
+    match get_deadline(e, project_hash) {
+        Some(deadline) => e.ledger().sequence() > deadline,
+        None => false,
+    }
+}
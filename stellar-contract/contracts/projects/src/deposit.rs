@@ -0,0 +1,93 @@
+use soroban_sdk::{token, Address, BytesN, Env};
+
+use crate::error::ProjectsError;
+use crate::fee;
+use crate::storage_types::{DepositConfig, ProjectDeposit, TreasuryKey};
+use crate::storage_types::{PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD};
+
+pub(crate) const MAX_FORFEIT_BPS: u32 = 10000;
+// keeps the forfeit computation of settle from overflowing
+pub(crate) const MAX_DEPOSIT_AMOUNT: i128 = i128::MAX / MAX_FORFEIT_BPS as i128;
+
+pub fn read_config(e: &Env) -> DepositConfig {
+    // no deposit is asked by default
+    e.storage().instance().get(&TreasuryKey::DepositConfig).unwrap_or(DepositConfig {
+        amount: 0,
+        forfeit_bps: 0,
+    })
+}
+
+pub fn write_config(e: &Env, config: &DepositConfig) -> Result<(), ProjectsError> {
+    if config.amount < 0 || config.amount > MAX_DEPOSIT_AMOUNT {
+        return Err(ProjectsError::InvalidAmount);
+    }
+    if config.forfeit_bps > MAX_FORFEIT_BPS {
+        return Err(ProjectsError::InvalidForfeitRatio);
+    }
+    // deposits are posted in the fee token, so forfeits can go to the treasury
+    if config.amount > 0 && fee::read_fee(e).is_none() {
+        return Err(ProjectsError::FeeTokenNotSet);
+    }
+    e.storage().instance().set(&TreasuryKey::DepositConfig, config);
+    Ok(())
+}
+
+// the sum of the deposits of every project not decided yet
+pub fn read_held(e: &Env) -> i128 {
+    e.storage().instance().get(&TreasuryKey::DepositsHeld).unwrap_or(0)
+}
+
+fn write_held(e: &Env, amount: i128) {
+    e.storage().instance().set(&TreasuryKey::DepositsHeld, &amount);
+}
+
+pub fn get_deposit(e: &Env, project_hash: &BytesN<32>) -> Option<ProjectDeposit> {
+    let key = TreasuryKey::Deposit(project_hash.clone());
+    let deposit = e.storage().persistent().get::<_, ProjectDeposit>(&key);
+    if deposit.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    deposit
+}
+
+// moves the deposit from the submitter into the contract, nothing is asked
+// until a deposit amount is set
+pub fn collect(e: &Env, from: &Address, project_hash: &BytesN<32>) {
+    let config = read_config(e);
+    if config.amount == 0 {
+        return;
+    }
+    // the fee token can not be removed once set
+    let token = fee::read_fee(e).unwrap().token;
+    token::Client::new(e, &token).transfer(from, &e.current_contract_address(), &config.amount);
+    let deposit = ProjectDeposit {
+        depositor: from.clone(),
+        token,
+        amount: config.amount,
+    };
+    let key = TreasuryKey::Deposit(project_hash.clone());
+    e.storage().persistent().set(&key, &deposit);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    write_held(e, read_held(e) + config.amount);
+}
+
+// releases the deposit of a project, the forfeited part goes to the treasury
+// and the rest back to the depositor. Returns the deposit with the forfeited
+// amount, if the project had one
+pub fn settle(e: &Env, project_hash: &BytesN<32>, forfeit_bps: u32) -> Option<(ProjectDeposit, i128)> {
+    let deposit = get_deposit(e, project_hash)?;
+    e.storage().persistent().remove(&TreasuryKey::Deposit(project_hash.clone()));
+    write_held(e, read_held(e) - deposit.amount);
+
+    let forfeited = deposit.amount * forfeit_bps as i128 / MAX_FORFEIT_BPS as i128;
+    fee::credit(e, forfeited);
+    let refunded = deposit.amount - forfeited;
+    if refunded > 0 {
+        token::Client::new(e, &deposit.token).transfer(&e.current_contract_address(), &deposit.depositor, &refunded);
+    }
+    Some((deposit, forfeited))
+}
@@ -0,0 +1,45 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ProjectsError {
+    NotInitialized = 1,
+    ProjectAlreadyExists = 2,
+    NotWhitelisted = 3,
+    NotPending = 4,
+    NotRejected = 5,
+    NotApproved = 6,
+    InvalidScore = 7,
+    MigrationInProgress = 8,
+    InvalidMigrationBatch = 9,
+    NoMigrationPending = 10,
+    AlreadyReviewed = 11,
+    InvalidThreshold = 12,
+    InconsistentScore = 13,
+    InvalidWeights = 14,
+    UnknownWeightsVersion = 15,
+    ProjectNotFound = 16,
+    NotProjectOwner = 17,
+    NoPendingAdmin = 18,
+    AdminProposalExpired = 19,
+    Unauthorized = 20,
+    InvalidRole = 21,
+    Paused = 22,
+    InvalidPageSize = 23,
+    PageTooLarge = 24,
+    InvalidAssignment = 25,
+    NotAssigned = 26,
+    ConflictOfInterest = 27,
+    NotLatestVersion = 28,
+    InvalidMetadata = 29,
+    UnknownCategory = 30,
+    InvalidCategory = 31,
+    NotCategoryReviewer = 32,
+    InvalidAmount = 33,
+    InsufficientTreasury = 34,
+    FeeTokenInUse = 35,
+    InvalidForfeitRatio = 36,
+    FeeTokenNotSet = 37,
+    ReviewerStillAssigned = 38,
+}
@@ -0,0 +1,65 @@
+use soroban_sdk::{token, Address, Env};
+
+use crate::deposit;
+use crate::error::ProjectsError;
+use crate::storage_types::{SubmissionFee, TreasuryKey};
+
+pub fn read_fee(e: &Env) -> Option<SubmissionFee> {
+    e.storage().instance().get(&TreasuryKey::SubmissionFee)
+}
+
+pub fn write_fee(e: &Env, fee: &SubmissionFee) -> Result<(), ProjectsError> {
+    if fee.amount < 0 {
+        return Err(ProjectsError::InvalidAmount);
+    }
+    // the treasury and the deposits are kept in a single token, so they must
+    // be emptied first
+    if let Some(current) = read_fee(e) {
+        if current.token != fee.token && (read_treasury(e) > 0 || deposit::read_held(e) > 0) {
+            return Err(ProjectsError::FeeTokenInUse);
+        }
+    }
+    e.storage().instance().set(&TreasuryKey::SubmissionFee, fee);
+    Ok(())
+}
+
+pub fn read_treasury(e: &Env) -> i128 {
+    e.storage().instance().get(&TreasuryKey::TreasuryBalance).unwrap_or(0)
+}
+
+fn write_treasury(e: &Env, amount: i128) {
+    e.storage().instance().set(&TreasuryKey::TreasuryBalance, &amount);
+}
+
+// adds tokens already held by the contract to the treasury
+pub fn credit(e: &Env, amount: i128) {
+    if amount > 0 {
+        write_treasury(e, read_treasury(e) + amount);
+    }
+}
+
+// moves the fee from the submitter into the treasury, submissions are free
+// until a fee is set
+pub fn charge(e: &Env, from: &Address) {
+    if let Some(fee) = read_fee(e) {
+        if fee.amount > 0 {
+            token::Client::new(e, &fee.token).transfer(from, &e.current_contract_address(), &fee.amount);
+            write_treasury(e, read_treasury(e) + fee.amount);
+        }
+    }
+}
+
+pub fn withdraw(e: &Env, to: &Address, amount: i128) -> Result<(), ProjectsError> {
+    if amount <= 0 {
+        return Err(ProjectsError::InvalidAmount);
+    }
+    let treasury = read_treasury(e);
+    if amount > treasury {
+        return Err(ProjectsError::InsufficientTreasury);
+    }
+    // the treasury is only ever filled when a fee is set
+    let fee = read_fee(e).ok_or(ProjectsError::InsufficientTreasury)?;
+    write_treasury(e, treasury - amount);
+    token::Client::new(e, &fee.token).transfer(&e.current_contract_address(), to, &amount);
+    Ok(())
+}
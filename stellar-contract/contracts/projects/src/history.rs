@@ -0,0 +1,63 @@
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+use crate::storage_types::{DataKey, ProjectStatusEnum, StatusChange};
+use crate::storage_types::{PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD};
+
+pub fn get_history_length(e: &Env, project_hash: &BytesN<32>) -> u32 {
+    let key = DataKey::HistoryLength(project_hash.clone());
+    if let Some(length) = e.storage().persistent().get::<_, u32>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        length
+    } else {
+        0
+    }
+}
+
+// every entry has its own key so recording a change does not rewrite the
+// whole history of the project
+pub fn append(
+    e: &Env,
+    project_hash: &BytesN<32>,
+    from: &ProjectStatusEnum,
+    to: &ProjectStatusEnum,
+    actor: Option<&Address>,
+    reason_hash: Option<&BytesN<32>>,
+) {
+    let change = StatusChange {
+        from: from.clone(),
+        to: to.clone(),
+        actor: actor.cloned(),
+        ledger: e.ledger().sequence(),
+        timestamp: e.ledger().timestamp(),
+        reason_hash: reason_hash.cloned(),
+    };
+    let length = get_history_length(e, project_hash);
+    let entry_key = DataKey::HistoryEntry(project_hash.clone(), length);
+    e.storage().persistent().set(&entry_key, &change);
+    e.storage()
+        .persistent()
+        .extend_ttl(&entry_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    let length_key = DataKey::HistoryLength(project_hash.clone());
+    e.storage().persistent().set(&length_key, &(length + 1));
+    e.storage()
+        .persistent()
+        .extend_ttl(&length_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+pub fn get_history(e: &Env, project_hash: &BytesN<32>, start: u32, limit: u32) -> Vec<StatusChange> {
+    let length = get_history_length(e, project_hash);
+    let end = length.min(start.saturating_add(limit));
+    let mut result = Vec::new(e);
+    for i in start..end {
+        let key = DataKey::HistoryEntry(project_hash.clone(), i);
+        if let Some(change) = e.storage().persistent().get::<_, StatusChange>(&key) {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+            result.push_back(change);
+        }
+    }
+    result
+}
@@ -1,10 +1,27 @@
 #![no_std]
 
 mod admin;
-mod whitelist;
+mod role;
 mod project;
+mod history;
+mod version;
+mod metadata;
+mod category;
+mod fee;
+mod deposit;
+mod review;
+mod assignment;
+mod conflict;
+mod deadline;
+mod scoring;
+mod migration;
+mod pause;
+mod page;
+mod upgrade;
 mod storage_types;
+mod error;
 mod contract;
 mod test;
 
-pub use crate::contract::ProjectsClient;
\ No newline at end of file
+pub use crate::contract::{Projects, ProjectsClient};
+pub use crate::error::ProjectsError;
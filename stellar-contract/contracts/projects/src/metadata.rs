@@ -0,0 +1,46 @@
+use soroban_sdk::{BytesN, Env};
+
+use crate::error::ProjectsError;
+use crate::storage_types::{DataKey, ProjectMetadata};
+use crate::storage_types::{PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD};
+
+// keeps the entry small, longer texts belong in the document itself
+pub(crate) const MAX_TITLE_LENGTH: u32 = 128;
+pub(crate) const MAX_DOCUMENT_URI_LENGTH: u32 = 256;
+// ISO 3166-1 alpha-2
+pub(crate) const COUNTRY_CODE_LENGTH: u32 = 2;
+
+pub fn validate_metadata(metadata: &ProjectMetadata) -> Result<(), ProjectsError> {
+    if metadata.title.is_empty() || metadata.title.len() > MAX_TITLE_LENGTH {
+        return Err(ProjectsError::InvalidMetadata);
+    }
+    if metadata.country_code.len() != COUNTRY_CODE_LENGTH {
+        return Err(ProjectsError::InvalidMetadata);
+    }
+    if metadata.document_uri.len() > MAX_DOCUMENT_URI_LENGTH {
+        return Err(ProjectsError::InvalidMetadata);
+    }
+    if metadata.funding_requested < 0 {
+        return Err(ProjectsError::InvalidMetadata);
+    }
+    Ok(())
+}
+
+pub fn set_metadata(e: &Env, project_hash: &BytesN<32>, metadata: &ProjectMetadata) {
+    let key = DataKey::ProjectMetadata(project_hash.clone());
+    e.storage().persistent().set(&key, metadata);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+pub fn get_metadata(e: &Env, project_hash: &BytesN<32>) -> Option<ProjectMetadata> {
+    let key = DataKey::ProjectMetadata(project_hash.clone());
+    let metadata = e.storage().persistent().get::<_, ProjectMetadata>(&key);
+    if metadata.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    metadata
+}
@@ -0,0 +1,117 @@
+use soroban_sdk::{Address, BytesN, Env, IntoVal, TryFromVal, Val, Vec};
+
+use crate::error::ProjectsError;
+use crate::project;
+use crate::role;
+use crate::upgrade::{read_schema_version, write_schema_version, SCHEMA_VERSION};
+use crate::storage_types::{DataKey, ProjectStatusEnum, Role, TrufaScoreValues};
+use crate::storage_types::{PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD};
+
+// keeps every batch well below the per-transaction ledger entry limits
+pub(crate) const MIGRATION_MAX_BATCH_SIZE: u32 = 50;
+
+fn read_cursor(e: &Env) -> u32 {
+    e.storage().instance().get(&DataKey::MigrationCursor).unwrap_or(0)
+}
+
+fn move_to_persistent<V>(e: &Env, key: &DataKey) -> Option<V>
+where
+    V: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let value = e.storage().instance().get::<_, V>(key)?;
+    e.storage().instance().remove(key);
+    e.storage().persistent().set(key, &value);
+    e.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    Some(value)
+}
+
+// deployments created before the persistent layout keep their project index in
+// the instance storage, and deployments created before the status index have
+// projects missing from it. As long as the next project to migrate is in one of
+// these cases the migration is not finished
+pub fn is_pending(e: &Env) -> bool {
+    if read_schema_version(e) >= SCHEMA_VERSION {
+        return false;
+    }
+    let cursor = read_cursor(e);
+    if e.storage().instance().has(&DataKey::ProjectIndex(cursor)) {
+        return true;
+    }
+    // projects added after the upgrade are indexed already and always come last
+    match project::read_project_hash(e, cursor) {
+        Some(project_hash) => !project::is_status_indexed(e, &project_hash),
+        None => false,
+    }
+}
+
+pub fn get_cursor(e: &Env) -> u32 {
+    read_cursor(e)
+}
+
+// moves a whitelist entry of the first layout into the reviewer role members
+fn move_legacy_reviewer(e: &Env, account: &Address) {
+    let key = DataKey::Whitelist(account.clone());
+    let whitelisted = match e.storage().instance().get::<_, bool>(&key) {
+        Some(whitelisted) => whitelisted,
+        None => return,
+    };
+    // removed addresses were kept with a false value
+    e.storage().instance().remove(&key);
+    if whitelisted {
+        role::grant_role(e, &Role::Reviewer, account);
+    }
+}
+
+// the whitelist of the first layout can not be enumerated, so its addresses
+// are given along with the batches, as found in the ("whitelist", "added") events
+pub fn migrate(e: &Env, batch_start: u32, batch_size: u32, legacy_reviewers: &Vec<Address>) -> Result<u32, ProjectsError> {
+    // deployments with nothing left in an old layout still walk the index
+    // once, so the schema version ends up matching the layout in use
+    if read_schema_version(e) >= SCHEMA_VERSION {
+        return Err(ProjectsError::NoMigrationPending);
+    }
+    // batches must be applied in order so the cursor always points to the first
+    // project that is still in the old layout
+    let cursor = read_cursor(e);
+    if batch_start != cursor || batch_size == 0 || batch_size > MIGRATION_MAX_BATCH_SIZE {
+        return Err(ProjectsError::InvalidMigrationBatch);
+    }
+    if legacy_reviewers.len() > MIGRATION_MAX_BATCH_SIZE {
+        return Err(ProjectsError::InvalidMigrationBatch);
+    }
+
+    // whitelist entries are moved into the persistent reviewer role members
+    for reviewer in legacy_reviewers.iter() {
+        move_legacy_reviewer(e, &reviewer);
+    }
+
+    // the counter stays in the instance storage in both layouts
+    let len = project::get_number_of_projects(e);
+    let end = len.min(cursor.saturating_add(batch_size));
+    for i in cursor..end {
+        // records of the first layout are moved to the persistent storage
+        if let Some(project_hash) = move_to_persistent::<BytesN<32>>(e, &DataKey::ProjectIndex(i)) {
+            move_to_persistent::<ProjectStatusEnum>(e, &DataKey::ProjectStatus(project_hash.clone()));
+            move_to_persistent::<TrufaScoreValues>(e, &DataKey::TrufaScore(project_hash));
+        }
+        // then every project is added to the index of its status
+        let project_hash = match project::read_project_hash(e, i) {
+            Some(project_hash) => project_hash,
+            None => continue,
+        };
+        if !project::is_status_indexed(e, &project_hash) {
+            let status = project::get_recorded_project_status(e, &project_hash);
+            project::add_to_status_index(e, &project_hash, &status);
+        }
+    }
+
+    if end >= len {
+        e.storage().instance().remove(&DataKey::MigrationCursor);
+        write_schema_version(e, SCHEMA_VERSION);
+    } else {
+        e.storage().instance().set(&DataKey::MigrationCursor, &end);
+    }
+    Ok(end)
+}
@@ -0,0 +1,26 @@
+use soroban_sdk::Env;
+
+use crate::error::ProjectsError;
+use crate::storage_types::DataKey;
+
+// every item of a page costs at least two ledger reads, so pages are kept
+// well below the per-transaction read limits
+pub(crate) const DEFAULT_MAX_PAGE_SIZE: u32 = 50;
+pub(crate) const MAX_PAGE_SIZE_LIMIT: u32 = 100;
+
+pub fn read_max_page_size(e: &Env) -> u32 {
+    e.storage().instance().get(&DataKey::MaxPageSize).unwrap_or(DEFAULT_MAX_PAGE_SIZE)
+}
+
+pub fn write_max_page_size(e: &Env, max_page_size: u32) -> Result<(), ProjectsError> {
+    if max_page_size == 0 || max_page_size > MAX_PAGE_SIZE_LIMIT {
+        return Err(ProjectsError::InvalidPageSize);
+    }
+    e.storage().instance().set(&DataKey::MaxPageSize, &max_page_size);
+    Ok(())
+}
+
+// requests for more items than the maximum page size get a shorter page
+pub fn clamp_limit(e: &Env, limit: u32) -> u32 {
+    limit.min(read_max_page_size(e))
+}
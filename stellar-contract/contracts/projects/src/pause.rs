@@ -0,0 +1,11 @@
+use soroban_sdk::Env;
+
+use crate::storage_types::DataKey;
+
+pub fn is_paused(e: &Env) -> bool {
+    e.storage().instance().get(&DataKey::Paused).unwrap_or(false)
+}
+
+pub fn write_paused(e: &Env, paused: bool) {
+    e.storage().instance().set(&DataKey::Paused, &paused);
+}
@@ -1,27 +1,132 @@
-use soroban_sdk::{Env, BytesN, Vec};
+use soroban_sdk::{Address, Env, BytesN, Vec};
 
+use crate::deadline;
+use crate::history;
 use crate::storage_types::DataKey;
+use crate::storage_types::Page;
 use crate::storage_types::ProjectData;
+use crate::storage_types::ProjectRecord;
 use crate::storage_types::ProjectStatusEnum;
 use crate::storage_types::TrufaScoreValues;
+use crate::storage_types::{PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD};
+
+fn read_project_status(e: &Env, project_hash: &BytesN<32>) -> ProjectStatusEnum {
+    let key = DataKey::ProjectStatus(project_hash.clone());
+    if let Some(status) = e.storage().persistent().get::<_, ProjectStatusEnum>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        status
+    } else {
+        ProjectStatusEnum::NotSet
+    }
+}
+
+// projects still pending after their review deadline are reported as expired
+// until expire_projects records the transition
+pub fn read_effective_status(e: &Env, project_hash: &BytesN<32>) -> ProjectStatusEnum {
+    let status = read_project_status(e, project_hash);
+    if status == ProjectStatusEnum::Pending && deadline::is_overdue(e, project_hash) {
+        return ProjectStatusEnum::Expired;
+    }
+    status
+}
+
+pub fn read_project_hash(e: &Env, index: u32) -> Option<BytesN<32>> {
+    let key = DataKey::ProjectIndex(index);
+    let project_hash = e.storage().persistent().get::<_, BytesN<32>>(&key);
+    if project_hash.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    project_hash
+}
+
+pub fn get_count_by_status(e: &Env, status: &ProjectStatusEnum) -> u32 {
+    e.storage().instance().get(&DataKey::StatusCount(status.clone())).unwrap_or(0)
+}
+
+pub fn is_status_indexed(e: &Env, project_hash: &BytesN<32>) -> bool {
+    e.storage().persistent().has(&DataKey::StatusPosition(project_hash.clone()))
+}
+
+pub fn add_to_status_index(e: &Env, project_hash: &BytesN<32>, status: &ProjectStatusEnum) {
+    let count = get_count_by_status(e, status);
+    let index_key = DataKey::StatusIndex(status.clone(), count);
+    e.storage().persistent().set(&index_key, project_hash);
+    e.storage()
+        .persistent()
+        .extend_ttl(&index_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    let position_key = DataKey::StatusPosition(project_hash.clone());
+    e.storage().persistent().set(&position_key, &count);
+    e.storage()
+        .persistent()
+        .extend_ttl(&position_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    e.storage().instance().set(&DataKey::StatusCount(status.clone()), &(count + 1));
+}
+
+fn remove_from_status_index(e: &Env, project_hash: &BytesN<32>, status: &ProjectStatusEnum) {
+    let position_key = DataKey::StatusPosition(project_hash.clone());
+    let position = match e.storage().persistent().get::<_, u32>(&position_key) {
+        Some(position) => position,
+        None => return,
+    };
+    // move the last project of the status into the freed position
+    let last = get_count_by_status(e, status) - 1;
+    if position != last {
+        let last_key = DataKey::StatusIndex(status.clone(), last);
+        let last_hash: BytesN<32> = e.storage().persistent().get(&last_key).unwrap();
+        let index_key = DataKey::StatusIndex(status.clone(), position);
+        e.storage().persistent().set(&index_key, &last_hash);
+        e.storage()
+            .persistent()
+            .extend_ttl(&index_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        let last_position_key = DataKey::StatusPosition(last_hash);
+        e.storage().persistent().set(&last_position_key, &position);
+        e.storage()
+            .persistent()
+            .extend_ttl(&last_position_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    e.storage().persistent().remove(&DataKey::StatusIndex(status.clone(), last));
+    e.storage().persistent().remove(&position_key);
+    e.storage().instance().set(&DataKey::StatusCount(status.clone()), &last);
+}
 
 pub fn set_project_status(
     e: &Env,
     project_hash: &BytesN<32>,
     status: &ProjectStatusEnum,
+    actor: Option<&Address>,
+    reason_hash: Option<&BytesN<32>>,
 ) {
     let project_key = DataKey::ProjectStatus(project_hash.clone());
+    let previous = read_project_status(e, project_hash);
+    let is_new = previous == ProjectStatusEnum::NotSet;
+    e.storage().persistent().set(&project_key, status);
+    e.storage()
+        .persistent()
+        .extend_ttl(&project_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    // keep a trace of the transition
+    history::append(e, project_hash, &previous, status, actor, reason_hash);
+    // keep the per status index in sync
+    if previous != *status {
+        if !is_new {
+            remove_from_status_index(e, project_hash, &previous);
+        }
+        add_to_status_index(e, project_hash, status);
+    }
     // if project already exists, we just update its value
-    if e.storage().instance().get(&project_key).unwrap_or(ProjectStatusEnum::NotSet) != ProjectStatusEnum::NotSet {
-        e.storage().instance().set(&project_key, status);
+    if !is_new {
         return;
     }
-    // else, we set its value andcreate a new index
-    e.storage().instance().set(&project_key, status);
-
-    let len = e.storage().instance().get(&DataKey::ProjectIndexLength).unwrap_or(0u32);
+    // else, we create a new index, only the counter lives in the instance storage
+    let len = get_number_of_projects(e);
     let index_key = DataKey::ProjectIndex(len);
-    e.storage().instance().set(&index_key, project_hash);
+    e.storage().persistent().set(&index_key, project_hash);
+    e.storage()
+        .persistent()
+        .extend_ttl(&index_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
     e.storage().instance().set(&DataKey::ProjectIndexLength, &(len + 1));
 }
 
@@ -29,8 +134,15 @@ pub fn get_project_status(
     e: &Env,
     project_hash: &BytesN<32>,
 ) -> ProjectStatusEnum {
-    let key = DataKey::ProjectStatus(project_hash.clone());
-    e.storage().instance().get(&key).unwrap_or(ProjectStatusEnum::NotSet)
+    read_effective_status(e, project_hash)
+}
+
+// the status as it is stored, without looking at the review deadline
+pub fn get_recorded_project_status(
+    e: &Env,
+    project_hash: &BytesN<32>,
+) -> ProjectStatusEnum {
+    read_project_status(e, project_hash)
 }
 
 pub fn get_projects_statuses_in_bulk(
@@ -38,9 +150,7 @@ pub fn get_projects_statuses_in_bulk(
     start: u32,
     end: u32
 ) -> Vec<ProjectData> {
-    // this function will scalate and be very expensive in the future
-
-    let mut storage_end = e.storage().instance().get(&DataKey::ProjectIndexLength).unwrap_or(0);
+    let mut storage_end = get_number_of_projects(e);
     // if start is more than storage_end or end is lower than start, return an empty vector
     if start > storage_end || end <= start {
         return Vec::new(e);
@@ -48,15 +158,13 @@ pub fn get_projects_statuses_in_bulk(
     if storage_end > end {
         storage_end = end;
     }
-    let zero_hash = BytesN::<32>::from_array(&e, &[0u8; 32]);
+    let zero_hash = BytesN::<32>::from_array(e, &[0u8; 32]);
     let mut result = Vec::new(e);
     for i in start..storage_end {
         // get the hash from the index storage
-        let index_key = DataKey::ProjectIndex(i);
-        let project_hash = e.storage().instance().get::<_, BytesN<32>>(&index_key).unwrap_or(zero_hash.clone());
+        let project_hash = read_project_hash(e, i).unwrap_or(zero_hash.clone());
         // using the project hash, get the project status
-        let project_key = DataKey::ProjectStatus(project_hash.clone());
-        let project_status = e.storage().instance().get(&project_key).unwrap_or(ProjectStatusEnum::NotSet);
+        let project_status = read_effective_status(e, &project_hash);
         // add the project status and hash to the result
         result.push_back(ProjectData {
             hash: project_hash.clone(),
@@ -66,11 +174,44 @@ pub fn get_projects_statuses_in_bulk(
     result
 }
 
+pub fn get_projects_by_status(
+    e: &Env,
+    status: &ProjectStatusEnum,
+    cursor: u32,
+    limit: u32,
+) -> Vec<ProjectData> {
+    let count = get_count_by_status(e, status);
+    let end = count.min(cursor.saturating_add(limit));
+    let mut result = Vec::new(e);
+    for i in cursor..end {
+        let key = DataKey::StatusIndex(status.clone(), i);
+        if let Some(project_hash) = e.storage().persistent().get::<_, BytesN<32>>(&key) {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+            let project_status = read_effective_status(e, &project_hash);
+            result.push_back(ProjectData {
+                hash: project_hash,
+                status: project_status,
+            });
+        }
+    }
+    result
+}
+
+pub fn list_projects(e: &Env, cursor: u32, limit: u32) -> Page {
+    let len = get_number_of_projects(e);
+    let end = len.min(cursor.saturating_add(limit));
+    let items = get_projects_statuses_in_bulk(e, cursor, end);
+    // an empty page has no next one, so following the cursor always moves forward
+    let next_cursor = if cursor < end && end < len { Some(end) } else { None };
+    Page { items, next_cursor }
+}
+
 pub fn get_number_of_projects(
     e: &Env,
 ) -> u32 {
-    let len = e.storage().instance().get(&DataKey::ProjectIndexLength).unwrap_or(0);
-    len
+    e.storage().instance().get(&DataKey::ProjectIndexLength).unwrap_or(0)
 }
 
 pub fn get_projects_statuses_from_vec(
@@ -79,8 +220,7 @@ pub fn get_projects_statuses_from_vec(
 ) -> Vec<ProjectStatusEnum> {
     let mut result = Vec::new(e);
     for project_hash in project_hashes {
-        let project_key = DataKey::ProjectStatus(project_hash.clone());
-        let project_status = e.storage().instance().get(&project_key).unwrap_or(ProjectStatusEnum::NotSet);
+        let project_status = read_effective_status(e, &project_hash);
         result.push_back(project_status);
     }
     result
@@ -88,10 +228,95 @@ pub fn get_projects_statuses_from_vec(
 
 pub fn set_trufa_score(e: &Env, project_hash: &BytesN<32>, score: &TrufaScoreValues) {
     let key = DataKey::TrufaScore(project_hash.clone());
-    e.storage().instance().set(&key, score);
+    e.storage().persistent().set(&key, score);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
 }
 
-pub fn get_trufa_score(e: &Env, project_hash: &BytesN<32>) -> TrufaScoreValues {
+pub fn get_trufa_score(e: &Env, project_hash: &BytesN<32>) -> Option<TrufaScoreValues> {
     let key = DataKey::TrufaScore(project_hash.clone());
-    e.storage().instance().get(&key).unwrap()
-}
\ No newline at end of file
+    let score = e.storage().persistent().get::<_, TrufaScoreValues>(&key);
+    if score.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    score
+}
+
+pub fn set_project_record(e: &Env, project_hash: &BytesN<32>, submitter: &Address) {
+    let record = ProjectRecord {
+        submitter: submitter.clone(),
+        submitted_ledger: e.ledger().sequence(),
+        submitted_at: e.ledger().timestamp(),
+    };
+    let key = DataKey::ProjectRecord(project_hash.clone());
+    e.storage().persistent().set(&key, &record);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+
+    // add the project to the index of its submitter
+    let count = get_number_of_projects_by_owner(e, submitter);
+    let index_key = DataKey::OwnerProject(submitter.clone(), count);
+    e.storage().persistent().set(&index_key, project_hash);
+    e.storage()
+        .persistent()
+        .extend_ttl(&index_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    // per owner counters grow with the number of users, so they are not kept in the instance
+    let count_key = DataKey::OwnerProjectCount(submitter.clone());
+    e.storage().persistent().set(&count_key, &(count + 1));
+    e.storage()
+        .persistent()
+        .extend_ttl(&count_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+pub fn get_project_record(e: &Env, project_hash: &BytesN<32>) -> Option<ProjectRecord> {
+    // projects migrated from the instance storage have no record
+    let key = DataKey::ProjectRecord(project_hash.clone());
+    let record = e.storage().persistent().get::<_, ProjectRecord>(&key);
+    if record.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    record
+}
+
+pub fn get_number_of_projects_by_owner(e: &Env, owner: &Address) -> u32 {
+    let key = DataKey::OwnerProjectCount(owner.clone());
+    if let Some(count) = e.storage().persistent().get::<_, u32>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        count
+    } else {
+        0
+    }
+}
+
+pub fn get_projects_by_owner(
+    e: &Env,
+    owner: &Address,
+    start: u32,
+    limit: u32,
+) -> Vec<ProjectData> {
+    let count = get_number_of_projects_by_owner(e, owner);
+    let end = count.min(start.saturating_add(limit));
+    let mut result = Vec::new(e);
+    for i in start..end {
+        let key = DataKey::OwnerProject(owner.clone(), i);
+        if let Some(project_hash) = e.storage().persistent().get::<_, BytesN<32>>(&key) {
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+            let project_status = read_effective_status(e, &project_hash);
+            result.push_back(ProjectData {
+                hash: project_hash,
+                status: project_status,
+            });
+        }
+    }
+    result
+}
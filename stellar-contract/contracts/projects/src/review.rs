@@ -0,0 +1,100 @@
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+use crate::error::ProjectsError;
+use crate::storage_types::{DataKey, Rationale, Review, ReviewVote};
+use crate::storage_types::{PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD};
+
+pub fn read_threshold(e: &Env) -> u32 {
+    // a single vote decides by default
+    e.storage().instance().get(&DataKey::ReviewThreshold).unwrap_or(1)
+}
+
+pub fn write_threshold(e: &Env, threshold: u32) -> Result<(), ProjectsError> {
+    if threshold == 0 {
+        return Err(ProjectsError::InvalidThreshold);
+    }
+    e.storage().instance().set(&DataKey::ReviewThreshold, &threshold);
+    Ok(())
+}
+
+pub fn get_reviews(e: &Env, project_hash: &BytesN<32>) -> Vec<Review> {
+    let key = DataKey::ProjectReviews(project_hash.clone());
+    if let Some(reviews) = e.storage().persistent().get::<_, Vec<Review>>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        reviews
+    } else {
+        Vec::new(e)
+    }
+}
+
+pub fn add_review(e: &Env, project_hash: &BytesN<32>, reviewer: &Address, vote: &ReviewVote) -> Result<Vec<Review>, ProjectsError> {
+    let mut reviews = get_reviews(e, project_hash);
+    // each reviewer only has one vote per review round
+    if reviews.iter().any(|review| review.reviewer == *reviewer) {
+        return Err(ProjectsError::AlreadyReviewed);
+    }
+    reviews.push_back(Review {
+        reviewer: reviewer.clone(),
+        vote: vote.clone(),
+    });
+    let key = DataKey::ProjectReviews(project_hash.clone());
+    e.storage().persistent().set(&key, &reviews);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    Ok(reviews)
+}
+
+pub fn clear_reviews(e: &Env, project_hash: &BytesN<32>) {
+    let key = DataKey::ProjectReviews(project_hash.clone());
+    e.storage().persistent().remove(&key);
+}
+
+// returns the number of approve and reject votes
+pub fn count_votes(reviews: &Vec<Review>) -> (u32, u32) {
+    let mut approvals = 0;
+    let mut rejections = 0;
+    for review in reviews.iter() {
+        match review.vote {
+            ReviewVote::Approve(_) => approvals += 1,
+            ReviewVote::Reject(_) => rejections += 1,
+        }
+    }
+    (approvals, rejections)
+}
+
+fn write_rationale(e: &Env, key: &DataKey, rationale: &Rationale) {
+    e.storage().persistent().set(key, rationale);
+    e.storage()
+        .persistent()
+        .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+fn read_rationale(e: &Env, key: &DataKey) -> Option<Rationale> {
+    let rationale = e.storage().persistent().get::<_, Rationale>(key);
+    if rationale.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    rationale
+}
+
+// the rationale of the vote that rejected the project
+pub fn set_rejection_rationale(e: &Env, project_hash: &BytesN<32>, rationale: &Rationale) {
+    write_rationale(e, &DataKey::RejectionRationale(project_hash.clone()), rationale);
+}
+
+pub fn get_rejection_rationale(e: &Env, project_hash: &BytesN<32>) -> Option<Rationale> {
+    read_rationale(e, &DataKey::RejectionRationale(project_hash.clone()))
+}
+
+pub fn set_reset_rationale(e: &Env, project_hash: &BytesN<32>, rationale: &Rationale) {
+    write_rationale(e, &DataKey::ResetRationale(project_hash.clone()), rationale);
+}
+
+pub fn get_reset_rationale(e: &Env, project_hash: &BytesN<32>) -> Option<Rationale> {
+    read_rationale(e, &DataKey::ResetRationale(project_hash.clone()))
+}
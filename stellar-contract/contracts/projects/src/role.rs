@@ -0,0 +1,161 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::admin::read_administrator;
+use crate::storage_types::{DataKey, Role};
+use crate::storage_types::{PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD};
+
+// number of roles, bounds the walk through the role admin hierarchy
+const ROLE_COUNT: u32 = 5;
+
+fn is_member(e: &Env, role: &Role, account: &Address) -> bool {
+    let key = DataKey::RoleMemberIndex(role.clone(), account.clone());
+    if e.storage().persistent().has(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        true
+    } else {
+        false
+    }
+}
+
+fn is_legacy_whitelisted(e: &Env, account: &Address) -> bool {
+    // whitelist entries of old deployments are keyed by address only, so they
+    // are still read from the instance storage until migrate moves them
+    let key = DataKey::Whitelist(account.clone());
+    e.storage().instance().get(&key).unwrap_or(false)
+}
+
+pub fn has_role(e: &Env, role: &Role, account: &Address) -> bool {
+    match role {
+        // the super admin is the address stored as the contract admin
+        Role::SuperAdmin => read_administrator(e).map(|admin| admin == *account).unwrap_or(false),
+        Role::Reviewer => is_member(e, role, account) || is_legacy_whitelisted(e, account),
+        _ => is_member(e, role, account),
+    }
+}
+
+pub fn get_role_admin(e: &Env, role: &Role) -> Role {
+    let key = DataKey::RoleAdmin(role.clone());
+    e.storage().instance().get(&key).unwrap_or(match role {
+        // reviewers are managed by the whitelist managers by default
+        Role::Reviewer => Role::WhitelistManager,
+        _ => Role::SuperAdmin,
+    })
+}
+
+pub fn set_role_admin(e: &Env, role: &Role, admin_role: &Role) {
+    let key = DataKey::RoleAdmin(role.clone());
+    e.storage().instance().set(&key, admin_role);
+}
+
+// an account can manage a role if it holds its admin role or any role above it
+pub fn can_manage_role(e: &Env, role: &Role, account: &Address) -> bool {
+    if has_role(e, &Role::SuperAdmin, account) {
+        return true;
+    }
+    let mut admin_role = get_role_admin(e, role);
+    for _ in 0..ROLE_COUNT {
+        if has_role(e, &admin_role, account) {
+            return true;
+        }
+        if admin_role == Role::SuperAdmin {
+            break;
+        }
+        admin_role = get_role_admin(e, &admin_role);
+    }
+    false
+}
+
+pub fn get_role_member_count(e: &Env, role: &Role) -> u32 {
+    match role {
+        Role::SuperAdmin => 1,
+        _ => e.storage().instance().get(&DataKey::RoleMemberCount(role.clone())).unwrap_or(0),
+    }
+}
+
+pub fn get_role_member(e: &Env, role: &Role, index: u32) -> Option<Address> {
+    let key = DataKey::RoleMember(role.clone(), index);
+    let account = e.storage().persistent().get::<_, Address>(&key);
+    if account.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    account
+}
+
+pub fn get_role_members(e: &Env, role: &Role, start: u32, limit: u32) -> Vec<Address> {
+    let mut result = Vec::new(e);
+    if *role == Role::SuperAdmin {
+        if let (0, true, Ok(admin)) = (start, limit > 0, read_administrator(e)) {
+            result.push_back(admin);
+        }
+        return result;
+    }
+    let count = get_role_member_count(e, role);
+    let end = count.min(start.saturating_add(limit));
+    for i in start..end {
+        if let Some(account) = get_role_member(e, role, i) {
+            result.push_back(account);
+        }
+    }
+    result
+}
+
+// returns false if the account already had the role
+pub fn grant_role(e: &Env, role: &Role, account: &Address) -> bool {
+    if *role == Role::Reviewer {
+        e.storage().instance().remove(&DataKey::Whitelist(account.clone()));
+    }
+    if is_member(e, role, account) {
+        return false;
+    }
+    let count = get_role_member_count(e, role);
+    let index_key = DataKey::RoleMemberIndex(role.clone(), account.clone());
+    e.storage().persistent().set(&index_key, &count);
+    e.storage()
+        .persistent()
+        .extend_ttl(&index_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    let member_key = DataKey::RoleMember(role.clone(), count);
+    e.storage().persistent().set(&member_key, account);
+    e.storage()
+        .persistent()
+        .extend_ttl(&member_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    e.storage().instance().set(&DataKey::RoleMemberCount(role.clone()), &(count + 1));
+    true
+}
+
+// returns false if the account did not have the role
+pub fn revoke_role(e: &Env, role: &Role, account: &Address) -> bool {
+    let mut revoked = false;
+    if *role == Role::Reviewer && is_legacy_whitelisted(e, account) {
+        e.storage().instance().remove(&DataKey::Whitelist(account.clone()));
+        revoked = true;
+    }
+    let index_key = DataKey::RoleMemberIndex(role.clone(), account.clone());
+    let index = match e.storage().persistent().get::<_, u32>(&index_key) {
+        Some(index) => index,
+        None => return revoked,
+    };
+    // move the last member into the freed position so the list stays contiguous
+    let last = get_role_member_count(e, role) - 1;
+    if index != last {
+        let last_key = DataKey::RoleMember(role.clone(), last);
+        let last_account: Address = e.storage().persistent().get(&last_key).unwrap();
+        let member_key = DataKey::RoleMember(role.clone(), index);
+        e.storage().persistent().set(&member_key, &last_account);
+        e.storage()
+            .persistent()
+            .extend_ttl(&member_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        let last_index_key = DataKey::RoleMemberIndex(role.clone(), last_account);
+        e.storage().persistent().set(&last_index_key, &index);
+        e.storage()
+            .persistent()
+            .extend_ttl(&last_index_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    e.storage().persistent().remove(&DataKey::RoleMember(role.clone(), last));
+    e.storage().persistent().remove(&index_key);
+    e.storage().instance().set(&DataKey::RoleMemberCount(role.clone()), &last);
+    true
+}
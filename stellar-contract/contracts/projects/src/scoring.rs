@@ -0,0 +1,211 @@
+use soroban_sdk::{BytesN, Env, Vec};
+
+use crate::error::ProjectsError;
+use crate::storage_types::{DataKey, Review, ReviewVote, ReviewerScore, ScoreAggregation, ScoringWeights, TrufaScoreValues};
+use crate::storage_types::{PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD};
+
+pub(crate) const MAX_SCORE: u32 = 100;
+
+pub fn read_tolerance(e: &Env) -> u32 {
+    // allows the reviewer to round the overall score either way
+    e.storage().instance().get(&DataKey::ScoreTolerance).unwrap_or(1)
+}
+
+pub fn write_tolerance(e: &Env, tolerance: u32) -> Result<(), ProjectsError> {
+    if tolerance > MAX_SCORE {
+        return Err(ProjectsError::InvalidScore);
+    }
+    e.storage().instance().set(&DataKey::ScoreTolerance, &tolerance);
+    Ok(())
+}
+
+pub(crate) const TOTAL_WEIGHT_BPS: u32 = 10_000;
+
+fn default_weights() -> ScoringWeights {
+    ScoringWeights {
+        technical_feasibility: 2_500,
+        regulatory_compliance: 2_500,
+        financial_viability: 2_500,
+        environment_impact: 2_500,
+    }
+}
+
+pub fn read_weights_version(e: &Env) -> u32 {
+    e.storage().instance().get(&DataKey::ScoringWeightsVersion).unwrap_or(0)
+}
+
+pub fn read_weights(e: &Env, version: u32) -> Option<ScoringWeights> {
+    // version 0 are the equal weights used until the admin sets their own
+    if version == 0 {
+        return Some(default_weights());
+    }
+    let key = DataKey::ScoringWeights(version);
+    let weights = e.storage().persistent().get::<_, ScoringWeights>(&key);
+    if weights.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    weights
+}
+
+pub fn read_current_weights(e: &Env) -> ScoringWeights {
+    read_weights(e, read_weights_version(e)).unwrap_or(default_weights())
+}
+
+pub fn write_weights(e: &Env, weights: &ScoringWeights) -> Result<u32, ProjectsError> {
+    let total = weights.technical_feasibility as u64
+        + weights.regulatory_compliance as u64
+        + weights.financial_viability as u64
+        + weights.environment_impact as u64;
+    if total != TOTAL_WEIGHT_BPS as u64 {
+        return Err(ProjectsError::InvalidWeights);
+    }
+    // previous versions are kept so approved scores can be traced back to their weights
+    let version = read_weights_version(e) + 1;
+    let key = DataKey::ScoringWeights(version);
+    e.storage().persistent().set(&key, weights);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    e.storage().instance().set(&DataKey::ScoringWeightsVersion, &version);
+    Ok(version)
+}
+
+pub fn set_score_weights_version(e: &Env, project_hash: &BytesN<32>, version: u32) {
+    let key = DataKey::TrufaScoreWeights(project_hash.clone());
+    e.storage().persistent().set(&key, &version);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
+
+pub fn get_score_weights_version(e: &Env, project_hash: &BytesN<32>) -> Option<u32> {
+    let key = DataKey::TrufaScoreWeights(project_hash.clone());
+    let version = e.storage().persistent().get::<_, u32>(&key);
+    if version.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    }
+    version
+}
+
+// sum of the weighted components, rounded to the nearest integer
+pub fn weighted_overall(score: &TrufaScoreValues, weights: &ScoringWeights) -> u32 {
+    let sum = score.technical_feasibility as u64 * weights.technical_feasibility as u64
+        + score.regulatory_compliance as u64 * weights.regulatory_compliance as u64
+        + score.financial_viability as u64 * weights.financial_viability as u64
+        + score.environment_impact as u64 * weights.environment_impact as u64;
+    let total = TOTAL_WEIGHT_BPS as u64;
+    ((sum + total / 2) / total) as u32
+}
+
+pub fn validate_score(e: &Env, score: &TrufaScoreValues) -> Result<(), ProjectsError> {
+    if score.technical_feasibility > MAX_SCORE
+        || score.regulatory_compliance > MAX_SCORE
+        || score.financial_viability > MAX_SCORE
+        || score.environment_impact > MAX_SCORE
+        || score.overall_trufa_score > MAX_SCORE
+    {
+        return Err(ProjectsError::InvalidScore);
+    }
+    // the overall score must match the weighted components
+    let weights = read_current_weights(e);
+    if score.overall_trufa_score.abs_diff(weighted_overall(score, &weights)) > read_tolerance(e) {
+        return Err(ProjectsError::InconsistentScore);
+    }
+    Ok(())
+}
+
+pub fn read_aggregation(e: &Env) -> ScoreAggregation {
+    e.storage().instance().get(&DataKey::ScoreAggregation).unwrap_or(ScoreAggregation::Mean)
+}
+
+pub fn write_aggregation(e: &Env, aggregation: &ScoreAggregation) {
+    e.storage().instance().set(&DataKey::ScoreAggregation, aggregation);
+}
+
+pub fn reviewer_scores(e: &Env, reviews: &Vec<Review>) -> Vec<ReviewerScore> {
+    let mut result = Vec::new(e);
+    for review in reviews.iter() {
+        if let ReviewVote::Approve(score) = review.vote {
+            result.push_back(ReviewerScore {
+                reviewer: review.reviewer,
+                score,
+            });
+        }
+    }
+    result
+}
+
+fn sorted(e: &Env, values: &Vec<u32>) -> Vec<u32> {
+    // insertion sort, there is only one value per reviewer
+    let mut result: Vec<u32> = Vec::new(e);
+    for value in values.iter() {
+        let mut i = result.len();
+        while i > 0 && result.get_unchecked(i - 1) > value {
+            i -= 1;
+        }
+        result.insert(i, value);
+    }
+    result
+}
+
+fn mean(values: &Vec<u32>) -> u32 {
+    if values.is_empty() {
+        return 0;
+    }
+    let len = values.len() as u64;
+    let sum: u64 = values.iter().map(|value| value as u64).sum();
+    // rounded to the nearest integer
+    ((sum + len / 2) / len) as u32
+}
+
+fn aggregate(e: &Env, values: &Vec<u32>, aggregation: &ScoreAggregation) -> u32 {
+    let values = sorted(e, values);
+    let len = values.len();
+    match aggregation {
+        ScoreAggregation::Mean => mean(&values),
+        ScoreAggregation::Median => {
+            if len == 0 {
+                0
+            } else if len % 2 == 1 {
+                values.get_unchecked(len / 2)
+            } else {
+                mean(&values.slice(len / 2 - 1..len / 2 + 1))
+            }
+        }
+        ScoreAggregation::TrimmedMean => {
+            // the lowest and the highest values are discarded when there are enough of them
+            if len < 3 {
+                mean(&values)
+            } else {
+                mean(&values.slice(1..len - 1))
+            }
+        }
+    }
+}
+
+pub fn aggregate_scores(e: &Env, scores: &Vec<ReviewerScore>, aggregation: &ScoreAggregation, weights: &ScoringWeights) -> TrufaScoreValues {
+    let mut technical_feasibility = Vec::new(e);
+    let mut regulatory_compliance = Vec::new(e);
+    let mut financial_viability = Vec::new(e);
+    let mut environment_impact = Vec::new(e);
+    for reviewer_score in scores.iter() {
+        technical_feasibility.push_back(reviewer_score.score.technical_feasibility);
+        regulatory_compliance.push_back(reviewer_score.score.regulatory_compliance);
+        financial_viability.push_back(reviewer_score.score.financial_viability);
+        environment_impact.push_back(reviewer_score.score.environment_impact);
+    }
+    let mut score = TrufaScoreValues {
+        technical_feasibility: aggregate(e, &technical_feasibility, aggregation),
+        regulatory_compliance: aggregate(e, &regulatory_compliance, aggregation),
+        financial_viability: aggregate(e, &financial_viability, aggregation),
+        environment_impact: aggregate(e, &environment_impact, aggregation),
+        overall_trufa_score: 0,
+    };
+    // the overall score is derived by the contract instead of trusting the reviewers
+    score.overall_trufa_score = weighted_overall(&score, weights);
+    score
+}
@@ -1,15 +1,101 @@
-use soroban_sdk::{contracttype, Address, BytesN};
+use soroban_sdk::{contracttype, Address, BytesN, String, Symbol, Vec};
 
 pub(crate) const DAY_IN_LEDGERS: u32 = 17280;
 pub(crate) const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS;
 pub(crate) const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
 
+// project, index, score and role records live in persistent storage so the
+// instance entry only holds the admin, the settings and the counters
+pub(crate) const PERSISTENT_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+pub(crate) const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+#[derive(Clone, Debug, PartialEq)]
 #[contracttype]
 pub struct ProjectData {
     pub hash: BytesN<32>,
     pub status: ProjectStatusEnum,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ProjectMetadata {
+    pub title: String,
+    pub category: Symbol,
+    pub country_code: String,
+    // where the project document lives, like an IPFS CID
+    pub document_uri: String,
+    pub funding_requested: i128,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct SubmissionFee {
+    // a Stellar Asset Contract or any other token
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct DepositConfig {
+    pub amount: i128,
+    // the part of the deposit kept on rejection, in basis points
+    pub forfeit_bps: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ProjectDeposit {
+    pub depositor: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct StatusChange {
+    pub from: ProjectStatusEnum,
+    pub to: ProjectStatusEnum,
+    // None when the change was not made by an authorized address, like an expiry
+    pub actor: Option<Address>,
+    pub ledger: u32,
+    pub timestamp: u64,
+    pub reason_hash: Option<BytesN<32>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[contracttype]
+pub struct Page {
+    pub items: Vec<ProjectData>,
+    // the cursor of the next page, None on the last one
+    pub next_cursor: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum Role {
+    SuperAdmin,
+    WhitelistManager,
+    Reviewer,
+    ScoreAuditor,
+    Pauser
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct PendingAdmin {
+    pub address: Address,
+    pub expiration_ledger: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ProjectRecord {
+    pub submitter: Address,
+    pub submitted_ledger: u32,
+    pub submitted_at: u64,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[contracttype]
 pub struct TrufaScoreValues {
@@ -20,22 +106,139 @@ pub struct TrufaScoreValues {
     pub overall_trufa_score: u32
 }
 
+// weight of each TRUFA component in basis points, they must add up to 10000
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ScoringWeights {
+    pub technical_feasibility: u32,
+    pub regulatory_compliance: u32,
+    pub financial_viability: u32,
+    pub environment_impact: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum RejectionReason {
+    Incomplete,
+    Regulatory,
+    Financial,
+    Technical,
+    Environmental,
+    Other
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Rationale {
+    pub reason: RejectionReason,
+    // hash of the document explaining the decision, kept off-chain
+    pub document: Option<BytesN<32>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum ReviewVote {
+    Approve(TrufaScoreValues),
+    Reject(Rationale)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Review {
+    pub reviewer: Address,
+    pub vote: ReviewVote,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ReviewerScore {
+    pub reviewer: Address,
+    pub score: TrufaScoreValues,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum ScoreAggregation {
+    Mean,
+    Median,
+    TrimmedMean
+}
+
 #[derive(Clone, PartialEq, Debug)]
 #[contracttype]
 pub enum ProjectStatusEnum {
     NotSet,
     Pending,
     Approved,
-    Rejected
+    Rejected,
+    Withdrawn,
+    Expired
 }
 
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
     Admin,
+    // only read from the instance storage of deployments migrated from the first layout
     Whitelist(Address),
     ProjectStatus(BytesN<32>),
     ProjectIndex(u32),
     ProjectIndexLength,
-    TrufaScore(BytesN<32>)
+    TrufaScore(BytesN<32>),
+    MigrationCursor,
+    SchemaVersion,
+    ReviewThreshold,
+    ProjectReviews(BytesN<32>),
+    ScoreAggregation,
+    ScoreTolerance,
+    ScoringWeightsVersion,
+    ScoringWeights(u32),
+    TrufaScoreWeights(BytesN<32>),
+    ProjectRecord(BytesN<32>),
+    OwnerProjectCount(Address),
+    OwnerProject(Address, u32),
+    PendingAdmin,
+    RoleAdmin(Role),
+    RoleMemberCount(Role),
+    RoleMember(Role, u32),
+    RoleMemberIndex(Role, Address),
+    Paused,
+    StatusCount(ProjectStatusEnum),
+    StatusIndex(ProjectStatusEnum, u32),
+    StatusPosition(BytesN<32>),
+    MaxPageSize,
+    Assignees(BytesN<32>),
+    AssignedCount(Address),
+    AssignedProject(Address, u32),
+    AssignedPosition(Address, BytesN<32>),
+    AutoAssignCount,
+    AssignmentCursor,
+    Conflict(Address, Address),
+    ReviewWindow,
+    ReviewDeadline(BytesN<32>),
+    HistoryLength(BytesN<32>),
+    HistoryEntry(BytesN<32>, u32),
+    RejectionRationale(BytesN<32>),
+    ResetRationale(BytesN<32>),
+    VersionRoot(BytesN<32>),
+    Versions(BytesN<32>),
+    ProjectMetadata(BytesN<32>),
+    Categories,
+    CategoryReviewer(Symbol, Address),
+    ProjectCategory(BytesN<32>),
+    CategoryProjectCount(Symbol),
+    CategoryProject(Symbol, u32)
+}
+
+// a contract type can not have more than 50 cases, so the fee and treasury
+// keys live apart. Their names must not clash with the DataKey ones, as both
+// enums are stored the same way
+#[derive(Clone)]
+#[contracttype]
+pub enum TreasuryKey {
+    SubmissionFee,
+    TreasuryBalance,
+    DepositConfig,
+    DepositsHeld,
+    Deposit(BytesN<32>)
 }
@@ -1,10 +1,13 @@
 #![cfg(test)]
 extern crate std;
-use crate::{contract::Projects, ProjectsClient};
-use crate::storage_types::{ProjectStatusEnum, TrufaScoreValues};
+use crate::{contract::Projects, ProjectsClient, ProjectsError};
+use crate::admin::read_administrator;
+use crate::{project, role, upgrade};
+use crate::storage_types::{DataKey, DepositConfig, Page, ProjectData, ProjectDeposit, ProjectMetadata, ProjectStatusEnum, Rationale, RejectionReason, StatusChange, ReviewVote, Role, ScoreAggregation, ScoringWeights, SubmissionFee, TrufaScoreValues};
+use crate::storage_types::{INSTANCE_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD};
 use soroban_sdk::{
-    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
-    Address, Env, IntoVal, Symbol, BytesN, Vec, vec
+    testutils::{storage::Persistent as _, Address as _, AuthorizedFunction, AuthorizedInvocation, Events, Ledger, MockAuth, MockAuthInvoke},
+    token, Address, Env, IntoVal, String, Symbol, BytesN, Vec, vec
 };
 
 // add function to create addresses vector
@@ -18,8 +21,19 @@ fn create_addresses(e: &Env, &n: &u32) -> Vec<Address> {
 
 // add function to instanciate the contract
 fn create_projects_contract<'a>(e: &Env, admin: &Address, whitelist_addresses: Vec<Address>) -> ProjectsClient<'a> {
-    let project_id = e.register(Projects, (admin, whitelist_addresses));
-    ProjectsClient::new(e, &project_id)
+    let project_id = e.register(Projects, (admin, whitelist_addresses.clone()));
+    let client = ProjectsClient::new(e, &project_id);
+    // the initial reviewers review the projects of the default category
+    client.mock_all_auths().add_category(&default_category(e));
+    for address in whitelist_addresses {
+        client.mock_all_auths().add_category_reviewer(admin, &default_category(e), &address);
+    }
+    client
+}
+
+// add function to get the category used by most tests
+fn default_category(e: &Env) -> Symbol {
+    Symbol::new(e, "general")
 }
 
 #[test]
@@ -36,15 +50,15 @@ fn test_whitelist() {
 
     for address in whitelist_addresses {
         let is_whitelisted = client.is_whitelisted(&address);
-        assert_eq!(is_whitelisted, true);
+        assert!(is_whitelisted);
     }
 
     // add a new whitelisted address
     // first check that this address is not whitelisted
     let is_whitelisted = client.is_whitelisted(&addresses.get(6).unwrap());
-    assert_eq!(is_whitelisted, false);
+    assert!(!is_whitelisted);
     // add the address
-    client.add_to_whitelist(&addresses.get(5).unwrap());
+    client.add_to_whitelist(&admin, &addresses.get(5).unwrap());
     assert_eq!(
         env.auths(),
         std::vec![(
@@ -53,7 +67,7 @@ fn test_whitelist() {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
                     Symbol::new(&env, "add_to_whitelist"),
-                    (&addresses.get(5).unwrap(),).into_val(&env),
+                    (&admin, &addresses.get(5).unwrap()).into_val(&env),
                 )),
                 sub_invocations: std::vec![]
             }
@@ -61,10 +75,10 @@ fn test_whitelist() {
     );
     // check that this address is now whitelisted
     let is_whitelisted = client.is_whitelisted(&addresses.get(5).unwrap());
-    assert_eq!(is_whitelisted, true);
+    assert!(is_whitelisted);
 
     // try to add an address already whitelisted will just update its status in the key:value mapping
-    client.add_to_whitelist(&addresses.get(5).unwrap());
+    client.add_to_whitelist(&admin, &addresses.get(5).unwrap());
     assert_eq!(
         env.auths(),
         std::vec![(
@@ -73,7 +87,7 @@ fn test_whitelist() {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
                     Symbol::new(&env, "add_to_whitelist"),
-                    (&addresses.get(5).unwrap(),).into_val(&env),
+                    (&admin, &addresses.get(5).unwrap()).into_val(&env),
                 )),
                 sub_invocations: std::vec![]
             }
@@ -81,7 +95,7 @@ fn test_whitelist() {
     );
     // check that this address is now whitelisted
     let is_whitelisted = client.is_whitelisted(&addresses.get(5).unwrap());
-    assert_eq!(is_whitelisted, true);
+    assert!(is_whitelisted);
 }
 
 #[test]
@@ -106,7 +120,7 @@ fn test_add_project_and_approve() {
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::NotSet);
     // add project
-    client.add_project(&user1, &project1_hash);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
     assert_eq!(
         env.auths(),
         std::vec![(
@@ -115,7 +129,7 @@ fn test_add_project_and_approve() {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
                     Symbol::new(&env, "add_project"),
-                    (&user1, &project1_hash).into_val(&env),
+                    (&user1, &project1_hash, default_category(&env), None::<ProjectMetadata>).into_val(&env),
                 )),
                 sub_invocations: std::vec![]
             }
@@ -160,7 +174,7 @@ fn test_add_project_and_reject() {
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::NotSet);
     // add project
-    client.add_project(&user1, &project1_hash);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
     assert_eq!(
         env.auths(),
         std::vec![(
@@ -169,7 +183,7 @@ fn test_add_project_and_reject() {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
                     Symbol::new(&env, "add_project"),
-                    (&user1, &project1_hash).into_val(&env),
+                    (&user1, &project1_hash, default_category(&env), None::<ProjectMetadata>).into_val(&env),
                 )),
                 sub_invocations: std::vec![]
             }
@@ -179,7 +193,7 @@ fn test_add_project_and_reject() {
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::Pending);
     // reject the project used a whitelisted address
-    client.set_project_rejected(&whitelist_addresses.get(1).unwrap(), &project1_hash);
+    client.set_project_rejected(&whitelist_addresses.get(1).unwrap(), &project1_hash, &RejectionReason::Other, &None);
     assert_eq!(
         env.auths(),
         std::vec![(
@@ -188,7 +202,7 @@ fn test_add_project_and_reject() {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
                     Symbol::new(&env, "set_project_rejected"),
-                    (&whitelist_addresses.get(1).unwrap(), &project1_hash).into_val(&env),
+                    (&whitelist_addresses.get(1).unwrap(), &project1_hash, RejectionReason::Other, None::<BytesN<32>>).into_val(&env),
                 )),
                 sub_invocations: std::vec![]
             }
@@ -200,7 +214,6 @@ fn test_add_project_and_reject() {
 }
 
 #[test]
-#[should_panic(expected = "Address is not whitelisted")]
 fn test_add_project_and_failed_to_approve() {
     let env = Env::default();
     env.mock_all_auths();
@@ -222,7 +235,7 @@ fn test_add_project_and_failed_to_approve() {
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::NotSet);
     // add project
-    client.add_project(&user1, &project1_hash);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
     assert_eq!(
         env.auths(),
         std::vec![(
@@ -231,7 +244,7 @@ fn test_add_project_and_failed_to_approve() {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
                     Symbol::new(&env, "add_project"),
-                    (&user1, &project1_hash).into_val(&env),
+                    (&user1, &project1_hash, default_category(&env), None::<ProjectMetadata>).into_val(&env),
                 )),
                 sub_invocations: std::vec![]
             }
@@ -240,12 +253,12 @@ fn test_add_project_and_failed_to_approve() {
     // check that project status is now Pending
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::Pending);
-    // try to approve with non-whitelisted address should panic
-    client.set_project_approved(&addresses.get(7).unwrap(), &project1_hash, &trufa_scores);
+    // try to approve with non-whitelisted address should fail
+    let result = client.try_set_project_approved(&addresses.get(7).unwrap(), &project1_hash, &trufa_scores);
+    assert_eq!(result, Err(Ok(ProjectsError::NotWhitelisted)));
 }
 
 #[test]
-#[should_panic(expected = "Address is not whitelisted")]
 fn test_add_project_and_failed_to_reject() {
     let env = Env::default();
     env.mock_all_auths();
@@ -259,7 +272,7 @@ fn test_add_project_and_failed_to_reject() {
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::NotSet);
     // add project
-    client.add_project(&user1, &project1_hash);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
     assert_eq!(
         env.auths(),
         std::vec![(
@@ -268,7 +281,7 @@ fn test_add_project_and_failed_to_reject() {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
                     Symbol::new(&env, "add_project"),
-                    (&user1, &project1_hash).into_val(&env),
+                    (&user1, &project1_hash, default_category(&env), None::<ProjectMetadata>).into_val(&env),
                 )),
                 sub_invocations: std::vec![]
             }
@@ -277,12 +290,12 @@ fn test_add_project_and_failed_to_reject() {
     // check that project status is now Pending
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::Pending);
-    // try to reject with non-whitelisted address should panic
-    client.set_project_rejected(&addresses.get(7).unwrap(), &project1_hash);
+    // try to reject with non-whitelisted address should fail
+    let result = client.try_set_project_rejected(&addresses.get(7).unwrap(), &project1_hash, &RejectionReason::Other, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::NotWhitelisted)));
 }
 
 #[test]
-#[should_panic(expected = "Project is not pending")]
 fn test_approve_of_not_set_project() {
     let env = Env::default();
     env.mock_all_auths();
@@ -298,12 +311,12 @@ fn test_approve_of_not_set_project() {
         environment_impact: 75,
         overall_trufa_score: 75
     };
-    // try to approve not-set project should panic
-    client.set_project_approved(&whitelist_addresses.get(0).unwrap(), &project1_hash, &trufa_scores);
+    // try to approve not-set project should fail
+    let result = client.try_set_project_approved(&whitelist_addresses.get(0).unwrap(), &project1_hash, &trufa_scores);
+    assert_eq!(result, Err(Ok(ProjectsError::NotPending)));
 }
 
 #[test]
-#[should_panic(expected = "Project is not pending")]
 fn test_reject_of_not_set_project() {
     let env = Env::default();
     env.mock_all_auths();
@@ -313,12 +326,12 @@ fn test_reject_of_not_set_project() {
     let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
 
     let project1_hash = BytesN::from_array(&env, &[1; 32]);
-    // try to reject not-set project should panic
-    client.set_project_rejected(&whitelist_addresses.get(0).unwrap(), &project1_hash);
+    // try to reject not-set project should fail
+    let result = client.try_set_project_rejected(&whitelist_addresses.get(0).unwrap(), &project1_hash, &RejectionReason::Other, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::NotPending)));
 }
 
 #[test]
-#[should_panic(expected = "Project already exists")]
 fn test_add_project_twice() {
     let env = Env::default();
     env.mock_all_auths();
@@ -334,12 +347,12 @@ fn test_add_project_twice() {
     let project1_hash = BytesN::from_array(&env, &[1; 32]);
 
     // add project twice
-    client.add_project(&user1, &project1_hash);
-    client.add_project(&user1, &project1_hash);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    let result = client.try_add_project(&user1, &project1_hash, &default_category(&env), &None);
+    assert_eq!(result, Err(Ok(ProjectsError::ProjectAlreadyExists)));
 }
 
 #[test]
-#[should_panic(expected = "Project already exists")]
 fn test_add_project_already_rejected() {
     let env = Env::default();
     env.mock_all_auths();
@@ -357,21 +370,21 @@ fn test_add_project_already_rejected() {
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::NotSet);
     // add project
-    client.add_project(&user1, &project1_hash);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
     // check that project status is now Pending
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::Pending);
     // reject the project used a whitelisted address
-    client.set_project_rejected(&whitelist_addresses.get(1).unwrap(), &project1_hash);
+    client.set_project_rejected(&whitelist_addresses.get(1).unwrap(), &project1_hash, &RejectionReason::Other, &None);
     // check that project status is now Rejected
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::Rejected);
-    // add project again should panic
-    client.add_project(&user1, &project1_hash);
+    // add project again should fail
+    let result = client.try_add_project(&user1, &project1_hash, &default_category(&env), &None);
+    assert_eq!(result, Err(Ok(ProjectsError::ProjectAlreadyExists)));
 }
 
 #[test]
-#[should_panic(expected = "Project already exists")]
 fn test_add_project_already_accepted() {
     let env = Env::default();
     env.mock_all_auths();
@@ -396,7 +409,7 @@ fn test_add_project_already_accepted() {
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::NotSet);
     // add project
-    client.add_project(&user1, &project1_hash);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
     // check that project status is now Pending
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::Pending);
@@ -405,8 +418,9 @@ fn test_add_project_already_accepted() {
     // check that project status is now Accepted
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::Approved);
-    // add project again should panic
-    client.add_project(&user1, &project1_hash);
+    // add project again should fail
+    let result = client.try_add_project(&user1, &project1_hash, &default_category(&env), &None);
+    assert_eq!(result, Err(Ok(ProjectsError::ProjectAlreadyExists)));
 }
 
 #[test]
@@ -421,7 +435,7 @@ fn test_reset_a_rejected_project() {
     let project1_hash = BytesN::from_array(&env, &[1; 32]);
 
     // add project
-    client.add_project(&user1, &project1_hash);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
     assert_eq!(
         env.auths(),
         std::vec![(
@@ -430,14 +444,14 @@ fn test_reset_a_rejected_project() {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
                     Symbol::new(&env, "add_project"),
-                    (&user1, &project1_hash).into_val(&env),
+                    (&user1, &project1_hash, default_category(&env), None::<ProjectMetadata>).into_val(&env),
                 )),
                 sub_invocations: std::vec![]
             }
         )]
     );
     // reject the project used a whitelisted address
-    client.set_project_rejected(&whitelist_addresses.get(1).unwrap(), &project1_hash);
+    client.set_project_rejected(&whitelist_addresses.get(1).unwrap(), &project1_hash, &RejectionReason::Other, &None);
     assert_eq!(
         env.auths(),
         std::vec![(
@@ -446,7 +460,7 @@ fn test_reset_a_rejected_project() {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
                     Symbol::new(&env, "set_project_rejected"),
-                    (&whitelist_addresses.get(1).unwrap(), &project1_hash).into_val(&env),
+                    (&whitelist_addresses.get(1).unwrap(), &project1_hash, RejectionReason::Other, None::<BytesN<32>>).into_val(&env),
                 )),
                 sub_invocations: std::vec![]
             }
@@ -456,7 +470,7 @@ fn test_reset_a_rejected_project() {
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::Rejected);
     // reset the project
-    client.reset_project(&project1_hash);
+    client.reset_project(&admin, &project1_hash, &RejectionReason::Other, &None);
     assert_eq!(
         env.auths(),
         std::vec![(
@@ -465,7 +479,7 @@ fn test_reset_a_rejected_project() {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
                     Symbol::new(&env, "reset_project"),
-                    (&project1_hash,).into_val(&env),
+                    (&admin, &project1_hash, RejectionReason::Other, None::<BytesN<32>>).into_val(&env),
                 )),
                 sub_invocations: std::vec![]
             }
@@ -477,7 +491,6 @@ fn test_reset_a_rejected_project() {
 }
 
 #[test]
-#[should_panic(expected = "Project is not rejected")]
 fn test_reset_an_approved_project() {
     let env = Env::default();
     env.mock_all_auths();
@@ -496,14 +509,15 @@ fn test_reset_an_approved_project() {
     };
 
     // add project
-    client.add_project(&user1, &project1_hash);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
     // approve the project used a whitelisted address
     client.set_project_approved(&whitelist_addresses.get(1).unwrap(), &project1_hash, &trufa_scores);
     // check that project status is now Approved
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::Approved);
-    // reset the project should panic
-    client.reset_project(&project1_hash);
+    // reset the project should fail
+    let result = client.try_reset_project(&admin, &project1_hash, &RejectionReason::Other, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::NotRejected)));
 }
 
 #[test]
@@ -525,7 +539,7 @@ fn test_add_project_reject_reset_and_approve() {
     };
 
     // add project
-    client.add_project(&user1, &project1_hash);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
     assert_eq!(
         env.auths(),
         std::vec![(
@@ -534,14 +548,14 @@ fn test_add_project_reject_reset_and_approve() {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
                     Symbol::new(&env, "add_project"),
-                    (&user1, &project1_hash).into_val(&env),
+                    (&user1, &project1_hash, default_category(&env), None::<ProjectMetadata>).into_val(&env),
                 )),
                 sub_invocations: std::vec![]
             }
         )]
     );
     // reject the project used a whitelisted address
-    client.set_project_rejected(&whitelist_addresses.get(1).unwrap(), &project1_hash);
+    client.set_project_rejected(&whitelist_addresses.get(1).unwrap(), &project1_hash, &RejectionReason::Other, &None);
     assert_eq!(
         env.auths(),
         std::vec![(
@@ -550,7 +564,7 @@ fn test_add_project_reject_reset_and_approve() {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
                     Symbol::new(&env, "set_project_rejected"),
-                    (&whitelist_addresses.get(1).unwrap(), &project1_hash).into_val(&env),
+                    (&whitelist_addresses.get(1).unwrap(), &project1_hash, RejectionReason::Other, None::<BytesN<32>>).into_val(&env),
                 )),
                 sub_invocations: std::vec![]
             }
@@ -560,7 +574,7 @@ fn test_add_project_reject_reset_and_approve() {
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::Rejected);
     // reset the project
-    client.reset_project(&project1_hash);
+    client.reset_project(&admin, &project1_hash, &RejectionReason::Other, &None);
     assert_eq!(
         env.auths(),
         std::vec![(
@@ -569,7 +583,7 @@ fn test_add_project_reject_reset_and_approve() {
                 function: AuthorizedFunction::Contract((
                     client.address.clone(),
                     Symbol::new(&env, "reset_project"),
-                    (&project1_hash,).into_val(&env),
+                    (&admin, &project1_hash, RejectionReason::Other, None::<BytesN<32>>).into_val(&env),
                 )),
                 sub_invocations: std::vec![]
             }
@@ -619,10 +633,10 @@ fn test_add_two_different_project_approve_one_and_reject_other () {
     };
 
     // add project 1 and 2
-    client.add_project(&user1, &project1_hash);
-    client.add_project(&user1, &project2_hash);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
     // reject the project 1 used a whitelisted address
-    client.set_project_rejected(&whitelist_addresses.get(1).unwrap(), &project1_hash);
+    client.set_project_rejected(&whitelist_addresses.get(1).unwrap(), &project1_hash, &RejectionReason::Other, &None);
     // check that project 1 status is now Rejected
     let status = client.get_project_status(&project1_hash);
     assert_eq!(status, ProjectStatusEnum::Rejected);
@@ -661,15 +675,15 @@ fn test_get_all_project_statuses() {
     };
 
     // add projects 1 and 2 using user1
-    client.add_project(&user1, &project1_hash);
-    client.add_project(&user1, &project2_hash);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
     // add project 3 using user2
-    client.add_project(&user2, &project3_hash);
+    client.add_project(&user2, &project3_hash, &default_category(&env), &None);
 
     // set project 2 to approved
     client.set_project_approved(&whitelist_addresses.get(1).unwrap(), &project2_hash, &trufa_scores);
     // set project 3 to rejected
-    client.set_project_rejected(&whitelist_addresses.get(2).unwrap(), &project3_hash);
+    client.set_project_rejected(&whitelist_addresses.get(2).unwrap(), &project3_hash, &RejectionReason::Other, &None);
     // check that all projects statuses are returned
     let status = client.get_all_projects_statuses();
     assert_eq!(status.len(), 3);
@@ -704,14 +718,14 @@ fn test_get_project_statuses_from_vec() {
     };
 
     // add all projects using any user, is not relevant at all
-    client.add_project(&user1, &project1_hash);
-    client.add_project(&user1, &project2_hash);
-    client.add_project(&user2, &project3_hash);
-    client.add_project(&user1, &project4_hash);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
+    client.add_project(&user2, &project3_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project4_hash, &default_category(&env), &None);
 
     // set project 3 to rejected and project 1 to approved
     client.set_project_approved(&whitelist_addresses.get(1).unwrap(), &project1_hash, &trufa_scores);
-    client.set_project_rejected(&whitelist_addresses.get(2).unwrap(), &project3_hash);
+    client.set_project_rejected(&whitelist_addresses.get(2).unwrap(), &project3_hash, &RejectionReason::Other, &None);
     // get the status for projects 1,3 and 4
     let query_vec = vec![&env, project1_hash.clone(), project3_hash.clone(), project4_hash.clone()];
     let status = client.get_projects_statuses_from_vec(&query_vec);
@@ -758,16 +772,16 @@ fn test_get_projects_in_bulk() {
     };
 
     // add projects 1 and 2 using user1
-    client.add_project(&user1, &project1_hash);
-    client.add_project(&user1, &project2_hash);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
     // add project 3 and 4 using user2
-    client.add_project(&user2, &project3_hash);
-    client.add_project(&user2, &project4_hash);
+    client.add_project(&user2, &project3_hash, &default_category(&env), &None);
+    client.add_project(&user2, &project4_hash, &default_category(&env), &None);
 
     // set project 2 to approved
     client.set_project_approved(&whitelist_addresses.get(1).unwrap(), &project2_hash, &trufa_scores);
     // set project 3 to rejected
-    client.set_project_rejected(&whitelist_addresses.get(2).unwrap(), &project3_hash);
+    client.set_project_rejected(&whitelist_addresses.get(2).unwrap(), &project3_hash, &RejectionReason::Other, &None);
     // set project 4 to approved
     client.set_project_approved(&whitelist_addresses.get(1).unwrap(), &project4_hash, &trufa_scores2);
     // get the sub-vec from idx 1 to idx 10 (the code will just return the available number of projects)
@@ -798,7 +812,6 @@ fn test_get_projects_in_bulk() {
 }
 
 #[test]
-#[should_panic(expected = "Project is not approved")]
 fn test_get_trufa_score_of_not_approved_project() {
     let env = Env::default();
     env.mock_all_auths();
@@ -810,9 +823,2482 @@ fn test_get_trufa_score_of_not_approved_project() {
     let project1_hash = BytesN::from_array(&env, &[1; 32]);
 
     // add projects 1 and 2 using user1
-    client.add_project(&user1, &project1_hash);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+
+    // get trufa score of pending project should fail
+    let result = client.try_get_trufa_score(&project1_hash);
+    assert_eq!(result, Err(Ok(ProjectsError::NotApproved)));
+}
+
+#[test]
+fn test_project_records_are_persistent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let trufa_scores = TrufaScoreValues {
+        technical_feasibility: 70,
+        regulatory_compliance: 75,
+        financial_viability: 80,
+        environment_impact: 75,
+        overall_trufa_score: 75
+    };
+
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.set_project_approved(&whitelist_addresses.get(1).unwrap(), &project1_hash, &trufa_scores);
+
+    let keys = [
+        DataKey::ProjectStatus(project1_hash.clone()),
+        DataKey::ProjectIndex(0),
+        DataKey::TrufaScore(project1_hash.clone()),
+        DataKey::RoleMemberIndex(Role::Reviewer, whitelist_addresses.get(1).unwrap()),
+    ];
+    env.as_contract(&client.address, || {
+        for key in keys.iter() {
+            // every record lives in its own persistent entry with its own ttl
+            assert!(env.storage().persistent().has(key));
+            assert!(!env.storage().instance().has(key));
+            assert!(env.storage().persistent().get_ttl(key) >= PERSISTENT_LIFETIME_THRESHOLD);
+        }
+        // the counter is kept in the instance storage
+        assert!(env.storage().instance().has(&DataKey::ProjectIndexLength));
+    });
+
+    // reading a project some days later extends its ttl again
+    env.ledger().with_mut(|li| li.sequence_number += INSTANCE_LIFETIME_THRESHOLD);
+    assert_eq!(client.get_project_status(&project1_hash), ProjectStatusEnum::Approved);
+    env.as_contract(&client.address, || {
+        let key = DataKey::ProjectStatus(project1_hash.clone());
+        assert_eq!(env.storage().persistent().get_ttl(&key), PERSISTENT_BUMP_AMOUNT);
+    });
+
+    // removing an address from the whitelist frees its entry
+    client.remove_from_whitelist(&admin, &whitelist_addresses.get(1).unwrap());
+    assert!(!client.is_whitelisted(&whitelist_addresses.get(1).unwrap()));
+    env.as_contract(&client.address, || {
+        let key = DataKey::RoleMemberIndex(Role::Reviewer, whitelist_addresses.get(1).unwrap());
+        assert!(!env.storage().persistent().has(&key));
+    });
+}
+
+// writes the records the way deployments created before the persistent layout did
+fn seed_legacy_project(e: &Env, contract: &Address, index: u32, project_hash: &BytesN<32>, status: &ProjectStatusEnum, score: Option<TrufaScoreValues>) {
+    e.as_contract(contract, || {
+        e.storage().instance().set(&DataKey::ProjectStatus(project_hash.clone()), status);
+        e.storage().instance().set(&DataKey::ProjectIndex(index), project_hash);
+        e.storage().instance().set(&DataKey::ProjectIndexLength, &(index + 1));
+        if let Some(score) = score {
+            e.storage().instance().set(&DataKey::TrufaScore(project_hash.clone()), &score);
+        }
+    });
+}
+
+#[test]
+fn test_migrate_legacy_layout() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let legacy_reviewer = addresses.get(1).unwrap(); // takes 2nd address
+    let removed_reviewer = addresses.get(2).unwrap(); // takes 3rd address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, Vec::new(&env));
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project2_hash = BytesN::from_array(&env, &[2; 32]);
+    let project3_hash = BytesN::from_array(&env, &[3; 32]);
+    let project4_hash = BytesN::from_array(&env, &[4; 32]);
+    let trufa_scores = TrufaScoreValues {
+        technical_feasibility: 30,
+        regulatory_compliance: 40,
+        financial_viability: 50,
+        environment_impact: 40,
+        overall_trufa_score: 40
+    };
+
+    // seed the old layout
+    seed_legacy_project(&env, &client.address, 0, &project1_hash, &ProjectStatusEnum::Pending, None);
+    seed_legacy_project(&env, &client.address, 1, &project2_hash, &ProjectStatusEnum::Approved, Some(trufa_scores.clone()));
+    seed_legacy_project(&env, &client.address, 2, &project3_hash, &ProjectStatusEnum::Rejected, None);
+    env.as_contract(&client.address, || {
+        env.storage().instance().set(&DataKey::Whitelist(legacy_reviewer.clone()), &true);
+        // the first layout kept removed addresses with a false value
+        env.storage().instance().set(&DataKey::Whitelist(removed_reviewer.clone()), &false);
+        // old deployments did not track their schema version
+        env.storage().instance().remove(&DataKey::SchemaVersion);
+    });
+    assert_eq!(client.version(), 1);
+    let expected = [
+        (project1_hash.clone(), ProjectStatusEnum::Pending),
+        (project2_hash.clone(), ProjectStatusEnum::Approved),
+        (project3_hash.clone(), ProjectStatusEnum::Rejected),
+    ];
+    assert!(client.is_migration_pending());
+
+    // writes are refused while the migration is pending
+    let result = client.try_add_project(&user1, &project4_hash, &default_category(&env), &None);
+    assert_eq!(result, Err(Ok(ProjectsError::MigrationInProgress)));
+    let result = client.try_set_project_rejected(&legacy_reviewer, &project1_hash, &RejectionReason::Other, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::MigrationInProgress)));
+
+    // batches must start where the previous one ended
+    let result = client.try_migrate(&1, &2, &Vec::new(&env));
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidMigrationBatch)));
+    let result = client.try_migrate(&0, &0, &Vec::new(&env));
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidMigrationBatch)));
+
+    // migrate the first batch
+    let legacy_reviewers = vec![&env, legacy_reviewer.clone(), removed_reviewer.clone()];
+    let cursor = client.migrate(&0, &2, &legacy_reviewers);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "migrate"),
+                    (0u32, 2u32, legacy_reviewers.clone()).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(cursor, 2);
+    assert_eq!(client.get_migration_cursor(), 2);
+    // the whitelist entries were moved to the reviewer role members
+    env.as_contract(&client.address, || {
+        assert!(!env.storage().instance().has(&DataKey::Whitelist(legacy_reviewer.clone())));
+        assert!(!env.storage().instance().has(&DataKey::Whitelist(removed_reviewer.clone())));
+        assert!(env.storage().persistent().has(&DataKey::RoleMemberIndex(Role::Reviewer, legacy_reviewer.clone())));
+    });
+    assert_eq!(client.get_role_members(&Role::Reviewer, &0, &10), vec![&env, legacy_reviewer.clone()]);
+    assert!(!client.is_whitelisted(&removed_reviewer));
+    assert!(client.is_migration_pending());
+    let result = client.try_add_project(&user1, &project4_hash, &default_category(&env), &None);
+    assert_eq!(result, Err(Ok(ProjectsError::MigrationInProgress)));
+
+    // resume with the second batch, which is larger than the remaining projects
+    let cursor = client.migrate(&2, &10, &Vec::new(&env));
+    assert_eq!(cursor, 3);
+    assert!(!client.is_migration_pending());
+    assert_eq!(client.version(), 3);
+    let result = client.try_migrate(&3, &10, &Vec::new(&env));
+    assert_eq!(result, Err(Ok(ProjectsError::NoMigrationPending)));
+
+    // the migrated state is equivalent to the old one
+    let status = client.get_all_projects_statuses();
+    assert_eq!(status.len(), 3);
+    for (i, (project_hash, project_status)) in expected.iter().enumerate() {
+        assert_eq!(&status.get(i as u32).unwrap().hash, project_hash);
+        assert_eq!(&status.get(i as u32).unwrap().status, project_status);
+    }
+    assert_eq!(client.get_trufa_score(&project2_hash), trufa_scores);
+    env.as_contract(&client.address, || {
+        for (i, project_hash) in [project1_hash.clone(), project2_hash.clone(), project3_hash.clone()].iter().enumerate() {
+            let index_key = DataKey::ProjectIndex(i as u32);
+            let status_key = DataKey::ProjectStatus(project_hash.clone());
+            assert!(!env.storage().instance().has(&index_key));
+            assert!(!env.storage().instance().has(&status_key));
+            assert!(env.storage().persistent().has(&index_key));
+            assert!(env.storage().persistent().has(&status_key));
+        }
+        assert!(!env.storage().instance().has(&DataKey::TrufaScore(project2_hash.clone())));
+        assert!(!env.storage().instance().has(&DataKey::MigrationCursor));
+    });
+    // and every project was added to the index of its status
+    for (project_hash, project_status) in expected.iter() {
+        let projects = client.get_projects_by_status(project_status, &0, &10);
+        assert!(projects.contains(ProjectData { hash: project_hash.clone(), status: project_status.clone() }));
+    }
+
+    // writes work again, including reviews from addresses whitelisted in the old layout
+    assert!(client.is_whitelisted(&legacy_reviewer));
+    client.add_project(&user1, &project4_hash, &default_category(&env), &None);
+    client.set_project_rejected(&legacy_reviewer, &project1_hash, &RejectionReason::Other, &None);
+    let status = client.get_all_projects_statuses();
+    assert_eq!(status.len(), 4);
+    assert_eq!(status.get(0).unwrap().status, ProjectStatusEnum::Rejected);
+    assert_eq!(status.get(3).unwrap().hash, project4_hash);
+    assert_eq!(status.get(3).unwrap().status, ProjectStatusEnum::Pending);
+
+    // addresses whitelisted in the old layout are managed as any other reviewer
+    client.remove_from_whitelist(&admin, &legacy_reviewer);
+    assert!(!client.is_whitelisted(&legacy_reviewer));
+}
+
+#[test]
+fn test_migrate_without_legacy_state() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+
+    // new deployments never need a migration
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    assert!(!client.is_migration_pending());
+    let result = client.try_migrate(&0, &10, &Vec::new(&env));
+    assert_eq!(result, Err(Ok(ProjectsError::NoMigrationPending)));
+
+    // old deployments without any project keep working, and migrate only
+    // records the schema version
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    env.as_contract(&client.address, || {
+        env.storage().instance().remove(&DataKey::SchemaVersion);
+    });
+    assert_eq!(client.version(), 1);
+    assert!(!client.is_migration_pending());
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    assert_eq!(client.migrate(&0, &10, &Vec::new(&env)), 1);
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("migration", "completed").into_val(&env),
+                (1u32,).into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(client.version(), 3);
+    let result = client.try_migrate(&1, &10, &Vec::new(&env));
+    assert_eq!(result, Err(Ok(ProjectsError::NoMigrationPending)));
+}
+
+#[test]
+fn test_migrate_status_index() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project2_hash = BytesN::from_array(&env, &[2; 32]);
+    let project3_hash = BytesN::from_array(&env, &[3; 32]);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
+    client.set_project_rejected(&whitelist_addresses.get(0).unwrap(), &project1_hash, &RejectionReason::Other, &None);
+
+    // deployments on the second schema version have no status index
+    env.as_contract(&client.address, || {
+        for status in [ProjectStatusEnum::Pending, ProjectStatusEnum::Rejected] {
+            env.storage().instance().remove(&DataKey::StatusCount(status.clone()));
+            env.storage().persistent().remove(&DataKey::StatusIndex(status, 0));
+        }
+        env.storage().persistent().remove(&DataKey::StatusPosition(project1_hash.clone()));
+        env.storage().persistent().remove(&DataKey::StatusPosition(project2_hash.clone()));
+        env.storage().instance().set(&DataKey::SchemaVersion, &2u32);
+    });
+    assert!(client.is_migration_pending());
+    let result = client.try_add_project(&user1, &project3_hash, &default_category(&env), &None);
+    assert_eq!(result, Err(Ok(ProjectsError::MigrationInProgress)));
+
+    // the migration indexes the existing projects
+    assert_eq!(client.migrate(&0, &1, &Vec::new(&env)), 1);
+    assert!(client.is_migration_pending());
+    assert_eq!(client.migrate(&1, &1, &Vec::new(&env)), 2);
+    assert!(!client.is_migration_pending());
+    assert_eq!(client.version(), 3);
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Rejected), 1);
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Pending), 1);
+    client.add_project(&user1, &project3_hash, &default_category(&env), &None);
+    assert_eq!(
+        client.get_projects_by_status(&ProjectStatusEnum::Pending, &0, &10),
+        vec![
+            &env,
+            ProjectData { hash: project2_hash.clone(), status: ProjectStatusEnum::Pending },
+            ProjectData { hash: project3_hash.clone(), status: ProjectStatusEnum::Pending },
+        ]
+    );
+}
+
+// minimal contract exporting a `version` function that returns 4, see the
+// comments of test_wasms/upgraded.wat
+fn upgraded_wasm() -> std::vec::Vec<u8> {
+    wat::parse_str(include_str!("../test_wasms/upgraded.wat")).unwrap()
+}
+
+#[test]
+fn test_upgrade() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project2_hash = BytesN::from_array(&env, &[2; 32]);
+    let project3_hash = BytesN::from_array(&env, &[3; 32]);
+    let trufa_scores = TrufaScoreValues {
+        technical_feasibility: 70,
+        regulatory_compliance: 75,
+        financial_viability: 80,
+        environment_impact: 75,
+        overall_trufa_score: 75
+    };
+
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
+    client.set_project_approved(&whitelist_addresses.get(1).unwrap(), &project1_hash, &trufa_scores);
+    assert_eq!(client.version(), 3);
+
+    // a deployment upgraded from the second schema version has no status index
+    env.as_contract(&client.address, || {
+        for status in [ProjectStatusEnum::Pending, ProjectStatusEnum::Approved] {
+            env.storage().instance().remove(&DataKey::StatusCount(status.clone()));
+            env.storage().persistent().remove(&DataKey::StatusIndex(status, 0));
+        }
+        env.storage().persistent().remove(&DataKey::StatusPosition(project1_hash.clone()));
+        env.storage().persistent().remove(&DataKey::StatusPosition(project2_hash.clone()));
+        env.storage().instance().set(&DataKey::SchemaVersion, &2u32);
+    });
+
+    // writes and further upgrades wait for the migration
+    let new_wasm_hash = env.deployer().upload_contract_wasm(upgraded_wasm().as_slice());
+    let result = client.try_add_project(&user1, &project3_hash, &default_category(&env), &None);
+    assert_eq!(result, Err(Ok(ProjectsError::MigrationInProgress)));
+    assert_eq!(client.try_upgrade(&new_wasm_hash), Err(Ok(ProjectsError::MigrationInProgress)));
+    client.migrate(&0, &10, &Vec::new(&env));
+    client.add_project(&user1, &project3_hash, &default_category(&env), &None);
+
+    // upgrade the contract to the new code
+    client.upgrade(&new_wasm_hash);
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("contract", "upgraded").into_val(&env),
+                (new_wasm_hash.clone(), 3u32, 3u32).into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "upgrade"),
+                    (&new_wasm_hash,).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+
+    // the new code is running
+    assert_eq!(client.version(), 4);
+
+    // and every record survived the upgrade, the stored schema version is left
+    // for the migration of the new code to bump
+    env.as_contract(&client.address, || {
+        assert_eq!(upgrade::read_schema_version(&env), 3);
+        assert_eq!(read_administrator(&env), Ok(admin.clone()));
+        assert_eq!(project::get_number_of_projects(&env), 3);
+        assert_eq!(project::get_project_status(&env, &project1_hash), ProjectStatusEnum::Approved);
+        assert_eq!(project::get_project_status(&env, &project2_hash), ProjectStatusEnum::Pending);
+        assert_eq!(project::get_trufa_score(&env, &project1_hash), Some(trufa_scores.clone()));
+        assert!(role::has_role(&env, &Role::Reviewer, &whitelist_addresses.get(1).unwrap()));
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_upgrade_by_non_admin() {
+    let env = Env::default();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let new_wasm_hash = env.deployer().upload_contract_wasm(upgraded_wasm().as_slice());
+
+    // without the admin authorization the upgrade is refused by the host
+    assert_eq!(client.version(), 3);
+    client.upgrade(&new_wasm_hash);
+}
+
+#[test]
+fn test_review_quorum() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project2_hash = BytesN::from_array(&env, &[2; 32]);
+    let trufa_scores = TrufaScoreValues {
+        technical_feasibility: 70,
+        regulatory_compliance: 75,
+        financial_viability: 80,
+        environment_impact: 75,
+        overall_trufa_score: 75
+    };
+    let trufa_scores2 = TrufaScoreValues {
+        technical_feasibility: 30,
+        regulatory_compliance: 40,
+        financial_viability: 50,
+        environment_impact: 40,
+        overall_trufa_score: 40
+    };
+
+    // a threshold of zero is not valid
+    let result = client.try_set_review_threshold(&0);
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidThreshold)));
+    // require 2 votes to decide a project
+    client.set_review_threshold(&2);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "set_review_threshold"),
+                    (2u32,).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(client.get_review_threshold(), 2);
+
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
+
+    // the first approval keeps the project pending
+    let vote = ReviewVote::Approve(trufa_scores.clone());
+    let status = client.cast_review(&whitelist_addresses.get(0).unwrap(), &project1_hash, &vote);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            whitelist_addresses.get(0).unwrap(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "cast_review"),
+                    (&whitelist_addresses.get(0).unwrap(), &project1_hash, vote.clone()).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(status, ProjectStatusEnum::Pending);
+    assert_eq!(client.get_project_status(&project1_hash), ProjectStatusEnum::Pending);
+    // the same reviewer can not vote twice
+    let result = client.try_set_project_approved(&whitelist_addresses.get(0).unwrap(), &project1_hash, &trufa_scores);
+    assert_eq!(result, Err(Ok(ProjectsError::AlreadyReviewed)));
+    // non whitelisted addresses can not vote
+    let result = client.try_cast_review(&addresses.get(7).unwrap(), &project1_hash, &ReviewVote::Reject(Rationale { reason: RejectionReason::Other, document: None }));
+    assert_eq!(result, Err(Ok(ProjectsError::NotWhitelisted)));
+    // a rejection does not reach the threshold either
+    client.set_project_rejected(&whitelist_addresses.get(1).unwrap(), &project1_hash, &RejectionReason::Other, &None);
+    assert_eq!(client.get_project_status(&project1_hash), ProjectStatusEnum::Pending);
+    // the second approval approves the project with the mean of both approvals
+    let mean_scores = TrufaScoreValues {
+        technical_feasibility: 50,
+        regulatory_compliance: 58,
+        financial_viability: 65,
+        environment_impact: 58,
+        overall_trufa_score: 58
+    };
+    client.set_project_approved(&whitelist_addresses.get(2).unwrap(), &project1_hash, &trufa_scores2);
+    assert_eq!(client.get_project_status(&project1_hash), ProjectStatusEnum::Approved);
+    assert_eq!(client.get_trufa_score(&project1_hash), mean_scores);
+    let reviews = client.get_reviews(&project1_hash);
+    assert_eq!(reviews.len(), 3);
+    assert_eq!(reviews.get(1).unwrap().reviewer, whitelist_addresses.get(1).unwrap());
+    assert_eq!(reviews.get(1).unwrap().vote, ReviewVote::Reject(Rationale { reason: RejectionReason::Other, document: None }));
+    // decided projects do not accept more votes
+    let result = client.try_set_project_rejected(&whitelist_addresses.get(3).unwrap(), &project1_hash, &RejectionReason::Other, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::NotPending)));
 
-    // get trufa score of pending project should panic
-    client.get_trufa_score(&project1_hash);
+    // two rejections reject the project
+    client.set_project_rejected(&whitelist_addresses.get(0).unwrap(), &project2_hash, &RejectionReason::Other, &None);
+    assert_eq!(client.get_project_status(&project2_hash), ProjectStatusEnum::Pending);
+    let status = client.cast_review(&whitelist_addresses.get(1).unwrap(), &project2_hash, &ReviewVote::Reject(Rationale { reason: RejectionReason::Other, document: None }));
+    assert_eq!(status, ProjectStatusEnum::Rejected);
 
+    // resetting the project starts a new review round
+    client.reset_project(&admin, &project2_hash, &RejectionReason::Other, &None);
+    assert_eq!(client.get_reviews(&project2_hash).len(), 0);
+    client.set_project_approved(&whitelist_addresses.get(0).unwrap(), &project2_hash, &trufa_scores2);
+    client.set_project_approved(&whitelist_addresses.get(1).unwrap(), &project2_hash, &trufa_scores);
+    assert_eq!(client.get_project_status(&project2_hash), ProjectStatusEnum::Approved);
+    assert_eq!(client.get_trufa_score(&project2_hash), mean_scores);
 }
+
+#[test]
+fn test_aggregate_reviewer_scores() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let reviewer5 = addresses.get(6).unwrap(); // takes 7th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    client.add_to_whitelist(&admin, &reviewer5);
+    client.add_category_reviewer(&admin, &default_category(&env), &reviewer5);
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project2_hash = BytesN::from_array(&env, &[2; 32]);
+    let project3_hash = BytesN::from_array(&env, &[3; 32]);
+    let scores = [
+        TrufaScoreValues {
+            technical_feasibility: 70,
+            regulatory_compliance: 60,
+            financial_viability: 80,
+            environment_impact: 90,
+            overall_trufa_score: 75
+        },
+        TrufaScoreValues {
+            technical_feasibility: 50,
+            regulatory_compliance: 60,
+            financial_viability: 90,
+            environment_impact: 70,
+            overall_trufa_score: 68
+        },
+        TrufaScoreValues {
+            technical_feasibility: 90,
+            regulatory_compliance: 90,
+            financial_viability: 40,
+            environment_impact: 80,
+            overall_trufa_score: 75
+        },
+        TrufaScoreValues {
+            technical_feasibility: 10,
+            regulatory_compliance: 100,
+            financial_viability: 60,
+            environment_impact: 60,
+            overall_trufa_score: 58
+        },
+    ];
+
+    // mean is the default aggregation
+    assert_eq!(client.get_score_aggregation(), ScoreAggregation::Mean);
+    client.set_review_threshold(&3);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project3_hash, &default_category(&env), &None);
+    for project_hash in [&project1_hash, &project2_hash, &project3_hash] {
+        client.set_project_approved(&whitelist_addresses.get(0).unwrap(), project_hash, &scores[0]);
+        client.set_project_approved(&whitelist_addresses.get(1).unwrap(), project_hash, &scores[1]);
+    }
+
+    // mean of 3 reviewers
+    client.set_project_approved(&whitelist_addresses.get(2).unwrap(), &project1_hash, &scores[2]);
+    assert_eq!(
+        client.get_trufa_score(&project1_hash),
+        TrufaScoreValues {
+            technical_feasibility: 70,
+            regulatory_compliance: 70,
+            financial_viability: 70,
+            environment_impact: 80,
+            overall_trufa_score: 73
+        }
+    );
+    let reviewer_scores = client.get_reviewer_scores(&project1_hash);
+    assert_eq!(reviewer_scores.len(), 3);
+    for i in 0..3 {
+        assert_eq!(reviewer_scores.get(i).unwrap().reviewer, whitelist_addresses.get(i).unwrap());
+        assert_eq!(reviewer_scores.get(i).unwrap().score, scores[i as usize]);
+    }
+
+    // median of 3 reviewers
+    client.set_score_aggregation(&ScoreAggregation::Median);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "set_score_aggregation"),
+                    (ScoreAggregation::Median,).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    client.set_project_approved(&whitelist_addresses.get(2).unwrap(), &project2_hash, &scores[2]);
+    assert_eq!(
+        client.get_trufa_score(&project2_hash),
+        TrufaScoreValues {
+            technical_feasibility: 70,
+            regulatory_compliance: 60,
+            financial_viability: 80,
+            environment_impact: 80,
+            overall_trufa_score: 73
+        }
+    );
+
+    // trimmed mean of 4 reviewers, rejections are not part of the score
+    client.set_score_aggregation(&ScoreAggregation::TrimmedMean);
+    client.set_review_threshold(&4);
+    client.set_project_rejected(&reviewer5, &project3_hash, &RejectionReason::Other, &None);
+    client.set_project_approved(&whitelist_addresses.get(2).unwrap(), &project3_hash, &scores[2]);
+    assert_eq!(client.get_project_status(&project3_hash), ProjectStatusEnum::Pending);
+    client.set_project_approved(&whitelist_addresses.get(3).unwrap(), &project3_hash, &scores[3]);
+    assert_eq!(client.get_project_status(&project3_hash), ProjectStatusEnum::Approved);
+    assert_eq!(client.get_reviewer_scores(&project3_hash).len(), 4);
+    assert_eq!(
+        client.get_trufa_score(&project3_hash),
+        TrufaScoreValues {
+            technical_feasibility: 60,
+            regulatory_compliance: 75,
+            financial_viability: 70,
+            environment_impact: 75,
+            overall_trufa_score: 70
+        }
+    );
+
+    // the score of an approved project does not change with the aggregation
+    client.set_score_aggregation(&ScoreAggregation::Mean);
+    assert_eq!(client.get_trufa_score(&project2_hash).regulatory_compliance, 60);
+}
+
+#[test]
+fn test_validate_trufa_score() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let reviewer = whitelist_addresses.get(1).unwrap();
+
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+
+    // components over 100 are refused
+    let out_of_range = TrufaScoreValues {
+        technical_feasibility: 101,
+        regulatory_compliance: 75,
+        financial_viability: 80,
+        environment_impact: 75,
+        overall_trufa_score: 83
+    };
+    let result = client.try_set_project_approved(&reviewer, &project1_hash, &out_of_range);
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidScore)));
+    let out_of_range = TrufaScoreValues {
+        technical_feasibility: 100,
+        regulatory_compliance: 100,
+        financial_viability: 100,
+        environment_impact: 100,
+        overall_trufa_score: 120
+    };
+    let result = client.try_set_project_approved(&reviewer, &project1_hash, &out_of_range);
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidScore)));
+
+    // the overall score must match the components: 70 + 75 + 80 + 75 = 300 / 4 = 75
+    let inconsistent = TrufaScoreValues {
+        technical_feasibility: 70,
+        regulatory_compliance: 75,
+        financial_viability: 80,
+        environment_impact: 75,
+        overall_trufa_score: 90
+    };
+    let result = client.try_set_project_approved(&reviewer, &project1_hash, &inconsistent);
+    assert_eq!(result, Err(Ok(ProjectsError::InconsistentScore)));
+    let within_tolerance = TrufaScoreValues {
+        overall_trufa_score: 76,
+        ..inconsistent.clone()
+    };
+    let outside_tolerance = TrufaScoreValues {
+        overall_trufa_score: 77,
+        ..inconsistent.clone()
+    };
+    let result = client.try_set_project_approved(&reviewer, &project1_hash, &outside_tolerance);
+    assert_eq!(result, Err(Ok(ProjectsError::InconsistentScore)));
+    assert_eq!(client.get_project_status(&project1_hash), ProjectStatusEnum::Pending);
+
+    // the admin can change the tolerance
+    assert_eq!(client.get_score_tolerance(), 1);
+    let result = client.try_set_score_tolerance(&101);
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidScore)));
+    client.set_score_tolerance(&0);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "set_score_tolerance"),
+                    (0u32,).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    let result = client.try_set_project_approved(&reviewer, &project1_hash, &within_tolerance);
+    assert_eq!(result, Err(Ok(ProjectsError::InconsistentScore)));
+    client.set_score_tolerance(&2);
+    client.set_project_approved(&reviewer, &project1_hash, &outside_tolerance);
+    assert_eq!(client.get_project_status(&project1_hash), ProjectStatusEnum::Approved);
+}
+
+#[test]
+fn test_scoring_weights() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project2_hash = BytesN::from_array(&env, &[2; 32]);
+    let reviewer = whitelist_addresses.get(1).unwrap();
+    let equal_weights = ScoringWeights {
+        technical_feasibility: 2500,
+        regulatory_compliance: 2500,
+        financial_viability: 2500,
+        environment_impact: 2500,
+    };
+    let regulatory_weights = ScoringWeights {
+        technical_feasibility: 2000,
+        regulatory_compliance: 4000,
+        financial_viability: 2000,
+        environment_impact: 2000,
+    };
+
+    // projects approved with the default weights
+    assert_eq!(client.get_scoring_weights_version(), 0);
+    assert_eq!(client.get_scoring_weights(), equal_weights);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
+    let trufa_scores = TrufaScoreValues {
+        technical_feasibility: 70,
+        regulatory_compliance: 90,
+        financial_viability: 60,
+        environment_impact: 80,
+        overall_trufa_score: 75
+    };
+    client.set_project_approved(&reviewer, &project1_hash, &trufa_scores);
+    assert_eq!(client.get_score_weights_version(&project1_hash), 0);
+    let result = client.try_get_score_weights_version(&project2_hash);
+    assert_eq!(result, Err(Ok(ProjectsError::NotApproved)));
+
+    // weights must add up to 100%
+    let result = client.try_set_scoring_weights(&ScoringWeights {
+        environment_impact: 2500,
+        ..regulatory_weights.clone()
+    });
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidWeights)));
+    let version = client.set_scoring_weights(&regulatory_weights);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "set_scoring_weights"),
+                    (regulatory_weights.clone(),).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(version, 1);
+    assert_eq!(client.get_scoring_weights_version(), 1);
+    assert_eq!(client.get_scoring_weights(), regulatory_weights);
+
+    // the submitted overall is checked against the new weights: 14 + 36 + 12 + 16 = 78
+    let result = client.try_set_project_approved(&reviewer, &project2_hash, &trufa_scores);
+    assert_eq!(result, Err(Ok(ProjectsError::InconsistentScore)));
+    let trufa_scores2 = TrufaScoreValues {
+        overall_trufa_score: 79,
+        ..trufa_scores.clone()
+    };
+    client.set_project_approved(&reviewer, &project2_hash, &trufa_scores2);
+    // and the stored overall is the one derived by the contract
+    assert_eq!(client.get_trufa_score(&project2_hash).overall_trufa_score, 78);
+    assert_eq!(client.get_score_weights_version(&project2_hash), 1);
+
+    // older scores keep the weights they were computed with
+    assert_eq!(client.get_trufa_score(&project1_hash).overall_trufa_score, 75);
+    assert_eq!(client.get_scoring_weights_by_version(&0), equal_weights);
+    assert_eq!(client.get_scoring_weights_by_version(&1), regulatory_weights);
+    let result = client.try_get_scoring_weights_by_version(&2);
+    assert_eq!(result, Err(Ok(ProjectsError::UnknownWeightsVersion)));
+}
+
+#[test]
+fn test_project_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let user2 = addresses.get(6).unwrap(); // takes 7th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project2_hash = BytesN::from_array(&env, &[2; 32]);
+    let project3_hash = BytesN::from_array(&env, &[3; 32]);
+    let project4_hash = BytesN::from_array(&env, &[4; 32]);
+
+    // unknown projects have no owner
+    let result = client.try_get_project_owner(&project1_hash);
+    assert_eq!(result, Err(Ok(ProjectsError::ProjectNotFound)));
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 100;
+        li.timestamp = 12345;
+    });
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.add_project(&user2, &project2_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project3_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project4_hash, &default_category(&env), &None);
+    client.set_project_rejected(&whitelist_addresses.get(0).unwrap(), &project3_hash, &RejectionReason::Other, &None);
+
+    assert_eq!(client.get_project_owner(&project1_hash), user1);
+    assert_eq!(client.get_project_owner(&project2_hash), user2);
+    let record = client.get_project_record(&project1_hash);
+    assert_eq!(record.submitter, user1);
+    assert_eq!(record.submitted_ledger, 100);
+    assert_eq!(record.submitted_at, 12345);
+
+    // list the projects of each owner
+    assert_eq!(client.get_number_of_projects_by_owner(&user1), 3);
+    assert_eq!(client.get_number_of_projects_by_owner(&user2), 1);
+    assert_eq!(client.get_number_of_projects_by_owner(&admin), 0);
+    let projects = client.get_projects_by_owner(&user1, &0, &10);
+    assert_eq!(projects.len(), 3);
+    assert_eq!(projects.get(0).unwrap().hash, project1_hash);
+    assert_eq!(projects.get(1).unwrap().hash, project3_hash);
+    assert_eq!(projects.get(1).unwrap().status, ProjectStatusEnum::Rejected);
+    assert_eq!(projects.get(2).unwrap().hash, project4_hash);
+    // paginate
+    let projects = client.get_projects_by_owner(&user1, &1, &1);
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects.get(0).unwrap().hash, project3_hash);
+    let projects = client.get_projects_by_owner(&user1, &3, &10);
+    assert_eq!(projects.len(), 0);
+    let projects = client.get_projects_by_owner(&user2, &0, &10);
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects.get(0).unwrap().hash, project2_hash);
+}
+
+#[test]
+fn test_withdraw_project() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let user2 = addresses.get(6).unwrap(); // takes 7th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    // only the submitter can withdraw the project
+    let result = client.try_withdraw_project(&user2, &project1_hash);
+    assert_eq!(result, Err(Ok(ProjectsError::NotProjectOwner)));
+
+    client.withdraw_project(&user1, &project1_hash);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "withdraw_project"),
+                    (&user1, &project1_hash).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("project", "withdrawn").into_val(&env),
+                (user1.clone(), project1_hash.clone(), ProjectStatusEnum::Withdrawn).into_val(&env),
+            )
+        ]
+    );
+    assert_eq!(client.get_project_status(&project1_hash), ProjectStatusEnum::Withdrawn);
+
+    // withdrawn projects can not be reviewed, withdrawn again or added again
+    let result = client.try_set_project_rejected(&whitelist_addresses.get(0).unwrap(), &project1_hash, &RejectionReason::Other, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::NotPending)));
+    let result = client.try_withdraw_project(&user1, &project1_hash);
+    assert_eq!(result, Err(Ok(ProjectsError::NotPending)));
+    let result = client.try_add_project(&user1, &project1_hash, &default_category(&env), &None);
+    assert_eq!(result, Err(Ok(ProjectsError::ProjectAlreadyExists)));
+}
+
+#[test]
+fn test_withdraw_reviewed_project() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project2_hash = BytesN::from_array(&env, &[2; 32]);
+
+    // unknown projects can not be withdrawn
+    let result = client.try_withdraw_project(&user1, &project1_hash);
+    assert_eq!(result, Err(Ok(ProjectsError::ProjectNotFound)));
+
+    // neither decided ones
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.set_project_rejected(&whitelist_addresses.get(0).unwrap(), &project1_hash, &RejectionReason::Other, &None);
+    let result = client.try_withdraw_project(&user1, &project1_hash);
+    assert_eq!(result, Err(Ok(ProjectsError::NotPending)));
+
+    // a project with votes below the threshold is still pending
+    client.set_review_threshold(&2);
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
+    client.set_project_rejected(&whitelist_addresses.get(0).unwrap(), &project2_hash, &RejectionReason::Other, &None);
+    client.withdraw_project(&user1, &project2_hash);
+    assert_eq!(client.get_project_status(&project2_hash), ProjectStatusEnum::Withdrawn);
+}
+
+#[test]
+fn test_admin_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let new_admin = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+
+    // nothing to accept or cancel yet
+    let result = client.try_accept_admin();
+    assert_eq!(result, Err(Ok(ProjectsError::NoPendingAdmin)));
+    let result = client.try_cancel_admin_proposal();
+    assert_eq!(result, Err(Ok(ProjectsError::NoPendingAdmin)));
+
+    // propose a new admin, the current one stays in charge
+    client.propose_admin(&new_admin, &None);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "propose_admin"),
+                    (&new_admin, None::<u32>).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_pending_admin().unwrap().address, new_admin);
+
+    // the proposal can be cancelled
+    client.cancel_admin_proposal();
+    assert_eq!(client.get_pending_admin(), None);
+    let result = client.try_accept_admin();
+    assert_eq!(result, Err(Ok(ProjectsError::NoPendingAdmin)));
+
+    // propose again and accept with the new admin authorization
+    client.propose_admin(&new_admin, &None);
+    client.accept_admin();
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            new_admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "accept_admin"),
+                    ().into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+
+    // admin functions now require the new admin
+    let result = client.try_add_to_whitelist(&admin, &addresses.get(7).unwrap());
+    assert_eq!(result, Err(Ok(ProjectsError::Unauthorized)));
+    client.add_to_whitelist(&new_admin, &addresses.get(7).unwrap());
+    assert_eq!(env.auths().first().unwrap().0, new_admin);
+}
+
+#[test]
+fn test_admin_proposal_expiration() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let new_admin = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+
+    env.ledger().with_mut(|li| li.sequence_number = 100);
+    client.propose_admin(&new_admin, &Some(10));
+    assert_eq!(client.get_pending_admin().unwrap().expiration_ledger, Some(110));
+
+    // the proposal can be accepted until the expiration ledger
+    env.ledger().with_mut(|li| li.sequence_number = 111);
+    let result = client.try_accept_admin();
+    assert_eq!(result, Err(Ok(ProjectsError::AdminProposalExpired)));
+    assert_eq!(client.get_admin(), admin);
+
+    client.propose_admin(&new_admin, &Some(10));
+    env.ledger().with_mut(|li| li.sequence_number = 121);
+    client.accept_admin();
+    assert_eq!(client.get_admin(), new_admin);
+}
+
+#[test]
+fn test_accept_admin_requires_new_admin() {
+    let env = Env::default();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let new_admin = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+
+    client
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "propose_admin",
+                args: (&new_admin, None::<u32>).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .propose_admin(&new_admin, &None);
+
+    // the current admin can not accept on behalf of the new one
+    let result = client
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "accept_admin",
+                args: ().into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_accept_admin();
+    assert!(result.is_err());
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+fn test_roles() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let manager = addresses.get(6).unwrap(); // takes 7th address
+    let auditor = addresses.get(7).unwrap(); // takes 8th address
+    let reviewer = addresses.get(8).unwrap(); // takes 9th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+
+    // the constructor whitelist becomes the reviewer role
+    assert_eq!(client.get_role_member_count(&Role::Reviewer), 4);
+    assert_eq!(client.get_role_members(&Role::Reviewer, &0, &10), whitelist_addresses);
+    assert_eq!(client.get_role_members(&Role::Reviewer, &1, &2), whitelist_addresses.slice(1..3));
+    assert_eq!(client.get_role_members(&Role::SuperAdmin, &0, &10), vec![&env, admin.clone()]);
+    assert!(client.has_role(&Role::SuperAdmin, &admin));
+
+    // the default role admins
+    assert_eq!(client.get_role_admin(&Role::Reviewer), Role::WhitelistManager);
+    assert_eq!(client.get_role_admin(&Role::ScoreAuditor), Role::SuperAdmin);
+
+    // only the role admin can grant a role
+    let result = client.try_grant_role(&user1, &Role::WhitelistManager, &manager);
+    assert_eq!(result, Err(Ok(ProjectsError::Unauthorized)));
+    client.grant_role(&admin, &Role::WhitelistManager, &manager);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "grant_role"),
+                    (&admin, Role::WhitelistManager, &manager).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("role", "granted").into_val(&env),
+                (Role::WhitelistManager, manager.clone(), admin.clone()).into_val(&env)
+            )
+        ]
+    );
+    assert!(client.has_role(&Role::WhitelistManager, &manager));
+
+    // whitelist managers manage the reviewers but no other role
+    client.add_to_whitelist(&manager, &reviewer);
+    client.add_category_reviewer(&manager, &default_category(&env), &reviewer);
+    assert!(client.has_role(&Role::Reviewer, &reviewer));
+    let result = client.try_grant_role(&manager, &Role::ScoreAuditor, &auditor);
+    assert_eq!(result, Err(Ok(ProjectsError::Unauthorized)));
+    let result = client.try_grant_role(&admin, &Role::SuperAdmin, &manager);
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidRole)));
+
+    // revoking swaps the last member into the freed position
+    client.revoke_role(&manager, &Role::Reviewer, &whitelist_addresses.get(0).unwrap());
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("role", "revoked").into_val(&env),
+                (Role::Reviewer, whitelist_addresses.get(0).unwrap(), manager.clone()).into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(client.get_role_member_count(&Role::Reviewer), 4);
+    let members = client.get_role_members(&Role::Reviewer, &0, &10);
+    assert_eq!(members.get(0).unwrap(), reviewer);
+    assert!(!members.contains(whitelist_addresses.get(0).unwrap()));
+
+    // reviewers can only review, score auditors can reset projects
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    let result = client.try_set_project_rejected(&whitelist_addresses.get(0).unwrap(), &project1_hash, &RejectionReason::Other, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::NotWhitelisted)));
+    client.set_project_rejected(&reviewer, &project1_hash, &RejectionReason::Other, &None);
+    let result = client.try_reset_project(&reviewer, &project1_hash, &RejectionReason::Other, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::Unauthorized)));
+    client.grant_role(&admin, &Role::ScoreAuditor, &auditor);
+    client.reset_project(&auditor, &project1_hash, &RejectionReason::Other, &None);
+    assert_eq!(client.get_project_status(&project1_hash), ProjectStatusEnum::Pending);
+
+    // the super admin can move the management of a role to another role
+    client.set_role_admin(&Role::ScoreAuditor, &Role::WhitelistManager);
+    assert_eq!(client.get_role_admin(&Role::ScoreAuditor), Role::WhitelistManager);
+    client.revoke_role(&manager, &Role::ScoreAuditor, &auditor);
+    assert!(!client.has_role(&Role::ScoreAuditor, &auditor));
+    assert_eq!(client.get_role_member_count(&Role::ScoreAuditor), 0);
+}
+
+#[test]
+fn test_pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let pauser = addresses.get(6).unwrap(); // takes 7th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project2_hash = BytesN::from_array(&env, &[2; 32]);
+    let reviewer = whitelist_addresses.get(0).unwrap();
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.set_project_rejected(&reviewer, &project1_hash, &RejectionReason::Other, &None);
+    assert!(!client.paused());
+
+    // only pausers and the super admin can pause
+    let result = client.try_pause(&pauser);
+    assert_eq!(result, Err(Ok(ProjectsError::Unauthorized)));
+    client.grant_role(&admin, &Role::Pauser, &pauser);
+    client.pause(&pauser);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            pauser.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "pause"),
+                    (&pauser,).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("contract", "paused").into_val(&env),
+                (pauser.clone(),).into_val(&env)
+            )
+        ]
+    );
+    assert!(client.paused());
+
+    // every write fails while paused
+    let trufa_scores = TrufaScoreValues {
+        technical_feasibility: 70,
+        regulatory_compliance: 70,
+        financial_viability: 70,
+        environment_impact: 70,
+        overall_trufa_score: 70
+    };
+    assert_eq!(client.try_add_project(&user1, &project2_hash, &default_category(&env), &None), Err(Ok(ProjectsError::Paused)));
+    assert_eq!(client.try_set_project_approved(&reviewer, &project1_hash, &trufa_scores), Err(Ok(ProjectsError::Paused)));
+    assert_eq!(client.try_set_project_rejected(&reviewer, &project1_hash, &RejectionReason::Other, &None), Err(Ok(ProjectsError::Paused)));
+    assert_eq!(client.try_reset_project(&admin, &project1_hash, &RejectionReason::Other, &None), Err(Ok(ProjectsError::Paused)));
+    assert_eq!(client.try_withdraw_project(&user1, &project1_hash), Err(Ok(ProjectsError::Paused)));
+    assert_eq!(client.try_add_to_whitelist(&admin, &user1), Err(Ok(ProjectsError::Paused)));
+    assert_eq!(client.try_remove_from_whitelist(&admin, &reviewer), Err(Ok(ProjectsError::Paused)));
+    assert_eq!(client.try_grant_role(&admin, &Role::ScoreAuditor, &user1), Err(Ok(ProjectsError::Paused)));
+    assert_eq!(client.try_revoke_role(&admin, &Role::Pauser, &pauser), Err(Ok(ProjectsError::Paused)));
+
+    // reads keep working
+    assert_eq!(client.get_project_status(&project1_hash), ProjectStatusEnum::Rejected);
+    assert!(client.is_whitelisted(&reviewer));
+    assert_eq!(client.get_all_projects_statuses().len(), 1);
+
+    // the super admin can unpause
+    client.unpause(&admin);
+    assert!(!client.paused());
+    client.reset_project(&admin, &project1_hash, &RejectionReason::Other, &None);
+    assert_eq!(client.get_project_status(&project1_hash), ProjectStatusEnum::Pending);
+}
+
+#[test]
+fn test_projects_by_status() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let reviewer = whitelist_addresses.get(0).unwrap();
+    let trufa_scores = TrufaScoreValues {
+        technical_feasibility: 70,
+        regulatory_compliance: 70,
+        financial_viability: 70,
+        environment_impact: 70,
+        overall_trufa_score: 70
+    };
+    let hashes: std::vec::Vec<BytesN<32>> = (1..=5u8).map(|i| BytesN::from_array(&env, &[i; 32])).collect();
+    for project_hash in hashes.iter() {
+        client.add_project(&user1, project_hash, &default_category(&env), &None);
+    }
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Pending), 5);
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Approved), 0);
+
+    // a decided project leaves the pending index and the last one takes its place
+    client.set_project_approved(&reviewer, &hashes[0], &trufa_scores);
+    client.set_project_rejected(&reviewer, &hashes[2], &RejectionReason::Other, &None);
+    client.withdraw_project(&user1, &hashes[3]);
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Pending), 2);
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Approved), 1);
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Rejected), 1);
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Withdrawn), 1);
+    assert_eq!(
+        client.get_projects_by_status(&ProjectStatusEnum::Pending, &0, &10),
+        vec![
+            &env,
+            ProjectData { hash: hashes[4].clone(), status: ProjectStatusEnum::Pending },
+            ProjectData { hash: hashes[1].clone(), status: ProjectStatusEnum::Pending },
+        ]
+    );
+
+    // pages are bounded by the cursor and the limit
+    assert_eq!(
+        client.get_projects_by_status(&ProjectStatusEnum::Pending, &1, &1),
+        vec![&env, ProjectData { hash: hashes[1].clone(), status: ProjectStatusEnum::Pending }]
+    );
+    assert_eq!(client.get_projects_by_status(&ProjectStatusEnum::Pending, &2, &10).len(), 0);
+
+    // a reset project goes back to the pending index
+    client.reset_project(&admin, &hashes[2], &RejectionReason::Other, &None);
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Rejected), 0);
+    assert_eq!(client.get_projects_by_status(&ProjectStatusEnum::Pending, &0, &10).len(), 3);
+}
+
+#[test]
+fn test_list_projects() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let hashes: std::vec::Vec<BytesN<32>> = (1..=5u8).map(|i| BytesN::from_array(&env, &[i; 32])).collect();
+    for project_hash in hashes.iter() {
+        client.add_project(&user1, project_hash, &default_category(&env), &None);
+    }
+    let pending = |i: usize| ProjectData { hash: hashes[i].clone(), status: ProjectStatusEnum::Pending };
+
+    // walk the projects page by page
+    assert_eq!(client.get_max_page_size(), 50);
+    let page = client.list_projects(&None, &2);
+    assert_eq!(page, Page { items: vec![&env, pending(0), pending(1)], next_cursor: Some(2) });
+    let page = client.list_projects(&page.next_cursor, &2);
+    assert_eq!(page, Page { items: vec![&env, pending(2), pending(3)], next_cursor: Some(4) });
+    let page = client.list_projects(&page.next_cursor, &2);
+    assert_eq!(page, Page { items: vec![&env, pending(4)], next_cursor: None });
+    assert_eq!(client.list_projects(&Some(10), &2).items.len(), 0);
+
+    // only the admin can change the maximum page size, within the hard limit
+    let result = client.try_set_max_page_size(&0);
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidPageSize)));
+    let result = client.try_set_max_page_size(&101);
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidPageSize)));
+    client.set_max_page_size(&3);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "set_max_page_size"),
+                    (3u32,).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(client.get_max_page_size(), 3);
+
+    // larger requests are cut to the maximum page size
+    let page = client.list_projects(&None, &10);
+    assert_eq!(page, Page { items: vec![&env, pending(0), pending(1), pending(2)], next_cursor: Some(3) });
+    // an empty page has no next cursor, so following it can not loop
+    let page = client.list_projects(&Some(1), &0);
+    assert_eq!(page, Page { items: Vec::new(&env), next_cursor: None });
+    assert_eq!(client.get_projects_statuses_in_bulk(&1, &10).len(), 3);
+    assert_eq!(client.get_projects_by_owner(&user1, &0, &10).len(), 3);
+    assert_eq!(client.get_projects_by_status(&ProjectStatusEnum::Pending, &0, &10).len(), 3);
+    assert_eq!(client.get_role_members(&Role::Reviewer, &0, &10).len(), 3);
+
+    // the unbounded getters refuse to go over the limit
+    let result = client.try_get_all_projects_statuses();
+    assert_eq!(result, Err(Ok(ProjectsError::PageTooLarge)));
+    let project_hashes = Vec::from_slice(&env, &hashes[..4]);
+    let result = client.try_get_projects_statuses_from_vec(&project_hashes);
+    assert_eq!(result, Err(Ok(ProjectsError::PageTooLarge)));
+    client.set_max_page_size(&5);
+    assert_eq!(client.get_all_projects_statuses().len(), 5);
+}
+
+#[test]
+fn test_assign_reviewers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let reviewer1 = whitelist_addresses.get(0).unwrap();
+    let reviewer2 = whitelist_addresses.get(1).unwrap();
+    let reviewer3 = whitelist_addresses.get(2).unwrap();
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project2_hash = BytesN::from_array(&env, &[2; 32]);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
+
+    // only reviewers can be assigned, once each
+    let result = client.try_assign_reviewers(&project1_hash, &vec![&env, user1.clone()]);
+    assert_eq!(result, Err(Ok(ProjectsError::NotWhitelisted)));
+    let result = client.try_assign_reviewers(&project1_hash, &vec![&env, reviewer1.clone(), reviewer1.clone()]);
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidAssignment)));
+
+    let reviewers = vec![&env, reviewer1.clone(), reviewer2.clone()];
+    client.assign_reviewers(&project1_hash, &reviewers);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "assign_reviewers"),
+                    (&project1_hash, reviewers.clone()).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("project", "assigned").into_val(&env),
+                (project1_hash.clone(), reviewers.clone()).into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(client.get_assigned_reviewers(&project1_hash), reviewers);
+    let queue = vec![&env, ProjectData { hash: project1_hash.clone(), status: ProjectStatusEnum::Pending }];
+    assert_eq!(client.get_assigned_projects(&reviewer1, &0, &10), queue);
+    assert_eq!(client.get_number_of_assigned_projects(&reviewer2), 1);
+    assert_eq!(client.get_number_of_assigned_projects(&reviewer3), 0);
+
+    // only the assignees can review an assigned project, other projects stay open
+    let result = client.try_set_project_rejected(&reviewer3, &project1_hash, &RejectionReason::Other, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::NotAssigned)));
+    client.set_project_rejected(&reviewer3, &project2_hash, &RejectionReason::Other, &None);
+
+    // a decided project leaves the queues, a reset one comes back
+    client.set_project_rejected(&reviewer2, &project1_hash, &RejectionReason::Other, &None);
+    assert_eq!(client.get_number_of_assigned_projects(&reviewer1), 0);
+    assert_eq!(client.get_number_of_assigned_projects(&reviewer2), 0);
+    client.reset_project(&admin, &project1_hash, &RejectionReason::Other, &None);
+    assert_eq!(client.get_assigned_projects(&reviewer1, &0, &10), queue);
+
+    // reassigning replaces the previous assignees
+    client.assign_reviewers(&project1_hash, &vec![&env, reviewer3.clone()]);
+    assert_eq!(client.get_number_of_assigned_projects(&reviewer1), 0);
+    assert_eq!(client.get_assigned_projects(&reviewer3, &0, &10), queue);
+
+    // there must be enough assignees to reach the threshold
+    client.set_review_threshold(&2);
+    let result = client.try_assign_reviewers(&project1_hash, &vec![&env, reviewer1.clone()]);
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidAssignment)));
+
+    // an empty list opens the project to every reviewer
+    client.assign_reviewers(&project1_hash, &Vec::new(&env));
+    assert_eq!(client.get_number_of_assigned_projects(&reviewer3), 0);
+    client.set_project_rejected(&reviewer1, &project1_hash, &RejectionReason::Other, &None);
+}
+
+#[test]
+fn test_auto_assign_reviewers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let hashes: std::vec::Vec<BytesN<32>> = (1..=3u8).map(|i| BytesN::from_array(&env, &[i; 32])).collect();
+
+    assert_eq!(client.get_auto_assign_count(), 0);
+    client.set_auto_assign_count(&3);
+    assert_eq!(client.get_auto_assign_count(), 3);
+
+    // every new project goes to the next reviewers in turn
+    for project_hash in hashes.iter() {
+        client.add_project(&user1, project_hash, &default_category(&env), &None);
+    }
+    let w = |i: u32| whitelist_addresses.get(i).unwrap();
+    assert_eq!(client.get_assigned_reviewers(&hashes[0]), vec![&env, w(0), w(1), w(2)]);
+    assert_eq!(client.get_assigned_reviewers(&hashes[1]), vec![&env, w(3), w(0), w(1)]);
+    assert_eq!(client.get_assigned_reviewers(&hashes[2]), vec![&env, w(2), w(3), w(0)]);
+    assert_eq!(client.get_number_of_assigned_projects(&w(0)), 3);
+    assert_eq!(client.get_number_of_assigned_projects(&w(3)), 2);
+
+    // the queue of a reviewer is paginated
+    let queue = client.get_assigned_projects(&w(0), &1, &1);
+    assert_eq!(queue, vec![&env, ProjectData { hash: hashes[1].clone(), status: ProjectStatusEnum::Pending }]);
+}
+
+#[test]
+fn test_auto_assign_below_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let energy = Symbol::new(&env, "energy");
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project2_hash = BytesN::from_array(&env, &[2; 32]);
+    let w = |i: u32| whitelist_addresses.get(i).unwrap();
+
+    // the automatic assignees must be able to reach the threshold
+    client.set_review_threshold(&3);
+    assert_eq!(client.try_set_auto_assign_count(&2), Err(Ok(ProjectsError::InvalidAssignment)));
+    client.set_auto_assign_count(&3);
+    assert_eq!(client.try_set_review_threshold(&4), Err(Ok(ProjectsError::InvalidThreshold)));
+    client.set_review_threshold(&2);
+    assert_eq!(client.get_review_threshold(), 2);
+
+    // a project gets the next reviewers while there are enough of them
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    assert_eq!(client.get_assigned_reviewers(&project1_hash), vec![&env, w(0), w(1), w(2)]);
+
+    // one of the two energy reviewers is affiliated to the submitter, so the
+    // project is left unassigned instead of waiting for a quorum forever
+    client.add_category(&energy);
+    client.add_category_reviewer(&admin, &energy, &w(3));
+    client.add_category_reviewer(&admin, &energy, &w(0));
+    client.declare_conflict(&w(0), &user1);
+    client.add_project(&user1, &project2_hash, &energy, &None);
+    assert_eq!(client.get_assigned_reviewers(&project2_hash), Vec::new(&env));
+    assert_eq!(client.get_number_of_assigned_projects(&w(3)), 0);
+}
+
+#[test]
+fn test_raise_threshold_after_assignment() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project2_hash = BytesN::from_array(&env, &[2; 32]);
+    let w = |i: u32| whitelist_addresses.get(i).unwrap();
+    let trufa_scores = TrufaScoreValues {
+        technical_feasibility: 70,
+        regulatory_compliance: 75,
+        financial_viability: 80,
+        environment_impact: 75,
+        overall_trufa_score: 75
+    };
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
+    client.set_review_threshold(&2);
+    client.assign_reviewers(&project1_hash, &vec![&env, w(0), w(1)]);
+
+    // the threshold is raised above the number of assignees of project1
+    client.set_review_threshold(&3);
+    let result = client.try_set_project_approved(&w(2), &project1_hash, &trufa_scores);
+    assert_eq!(result, Err(Ok(ProjectsError::NotAssigned)));
+
+    // every assignee agreeing still decides the assigned project
+    client.set_project_approved(&w(0), &project1_hash, &trufa_scores);
+    assert_eq!(client.get_project_status(&project1_hash), ProjectStatusEnum::Pending);
+    client.set_project_approved(&w(1), &project1_hash, &trufa_scores);
+    assert_eq!(client.get_project_status(&project1_hash), ProjectStatusEnum::Approved);
+    assert_eq!(client.get_number_of_assigned_projects(&w(0)), 0);
+
+    // projects open to every reviewer need the whole threshold
+    client.set_project_approved(&w(0), &project2_hash, &trufa_scores);
+    client.set_project_approved(&w(1), &project2_hash, &trufa_scores);
+    assert_eq!(client.get_project_status(&project2_hash), ProjectStatusEnum::Pending);
+    client.set_project_approved(&w(2), &project2_hash, &trufa_scores);
+    assert_eq!(client.get_project_status(&project2_hash), ProjectStatusEnum::Approved);
+}
+
+#[test]
+fn test_remove_assigned_reviewer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let w = |i: u32| whitelist_addresses.get(i).unwrap();
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.assign_reviewers(&project1_hash, &vec![&env, w(0), w(1)]);
+
+    // an assigned reviewer keeps its role while the project is pending
+    let result = client.try_remove_from_whitelist(&admin, &w(0));
+    assert_eq!(result, Err(Ok(ProjectsError::ReviewerStillAssigned)));
+    let result = client.try_revoke_role(&admin, &Role::Reviewer, &w(0));
+    assert_eq!(result, Err(Ok(ProjectsError::ReviewerStillAssigned)));
+    assert!(client.is_whitelisted(&w(0)));
+    // other roles of the reviewer are not concerned
+    client.grant_role(&admin, &Role::Pauser, &w(0));
+    client.revoke_role(&admin, &Role::Pauser, &w(0));
+
+    // once the project is reassigned the reviewer can be removed
+    client.assign_reviewers(&project1_hash, &vec![&env, w(1), w(2)]);
+    assert_eq!(client.get_number_of_assigned_projects(&w(0)), 0);
+    client.remove_from_whitelist(&admin, &w(0));
+    assert!(!client.is_whitelisted(&w(0)));
+
+    // and once the project is decided its assignees can be removed too
+    client.set_project_rejected(&w(1), &project1_hash, &RejectionReason::Other, &None);
+    client.revoke_role(&admin, &Role::Reviewer, &w(1));
+    assert!(!client.is_whitelisted(&w(1)));
+}
+
+#[test]
+fn test_conflict_of_interest() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let reviewer1 = whitelist_addresses.get(0).unwrap();
+    let reviewer2 = whitelist_addresses.get(1).unwrap();
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project2_hash = BytesN::from_array(&env, &[2; 32]);
+    let project3_hash = BytesN::from_array(&env, &[3; 32]);
+
+    // a reviewer can not review its own project
+    client.add_project(&reviewer1, &project1_hash, &default_category(&env), &None);
+    assert!(client.has_conflict(&reviewer1, &reviewer1));
+    let result = client.try_set_project_rejected(&reviewer1, &project1_hash, &RejectionReason::Other, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::ConflictOfInterest)));
+    let result = client.try_assign_reviewers(&project1_hash, &vec![&env, reviewer1.clone()]);
+    assert_eq!(result, Err(Ok(ProjectsError::ConflictOfInterest)));
+    client.set_project_rejected(&reviewer2, &project1_hash, &RejectionReason::Other, &None);
+
+    // nor the projects of a declared affiliation
+    client.declare_conflict(&reviewer1, &user1);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "declare_conflict"),
+                    (&reviewer1, &user1).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("conflict", "declared").into_val(&env),
+                (reviewer1.clone(), user1.clone()).into_val(&env)
+            )
+        ]
+    );
+    assert!(client.has_conflict(&reviewer1, &user1));
+    assert!(!client.has_conflict(&user1, &reviewer1));
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
+    let result = client.try_set_project_rejected(&reviewer1, &project2_hash, &RejectionReason::Other, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::ConflictOfInterest)));
+
+    // automatic assignment skips the conflicted reviewers
+    client.set_auto_assign_count(&2);
+    client.add_project(&user1, &project3_hash, &default_category(&env), &None);
+    assert_eq!(client.get_assigned_reviewers(&project3_hash), vec![&env, reviewer2.clone(), whitelist_addresses.get(2).unwrap()]);
+
+    // once removed the affiliation no longer blocks reviews
+    client.remove_conflict(&reviewer1, &user1);
+    assert!(!client.has_conflict(&reviewer1, &user1));
+    client.set_project_rejected(&reviewer1, &project2_hash, &RejectionReason::Other, &None);
+    assert_eq!(client.get_project_status(&project2_hash), ProjectStatusEnum::Rejected);
+}
+
+#[test]
+fn test_expire_projects() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let reviewer = whitelist_addresses.get(0).unwrap();
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project2_hash = BytesN::from_array(&env, &[2; 32]);
+    let project3_hash = BytesN::from_array(&env, &[3; 32]);
+
+    // projects added without a review window never expire
+    assert_eq!(client.get_review_window(), 0);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    assert_eq!(client.get_review_deadline(&project1_hash), None);
+
+    client.set_review_window(&100);
+    assert_eq!(client.get_review_window(), 100);
+    let start = env.ledger().sequence();
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project3_hash, &default_category(&env), &None);
+    assert_eq!(client.get_review_deadline(&project2_hash), Some(start + 100));
+
+    // the last ledger of the window can still be used to review
+    env.ledger().with_mut(|li| li.sequence_number = start + 100);
+    assert_eq!(client.get_project_status(&project2_hash), ProjectStatusEnum::Pending);
+    client.set_project_rejected(&reviewer, &project3_hash, &RejectionReason::Other, &None);
+
+    // after the deadline the project is reported as expired and can not be reviewed
+    env.ledger().with_mut(|li| li.sequence_number = start + 101);
+    assert_eq!(client.get_project_status(&project2_hash), ProjectStatusEnum::Expired);
+    assert_eq!(client.get_project_status(&project1_hash), ProjectStatusEnum::Pending);
+    let result = client.try_set_project_rejected(&reviewer, &project2_hash, &RejectionReason::Other, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::NotPending)));
+
+    // anyone can record the transition
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Pending), 2);
+    let next_cursor = client.expire_projects(&0, &10);
+    assert_eq!(next_cursor, None);
+    assert!(env.auths().is_empty());
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("project", "expired").into_val(&env),
+                (project2_hash.clone(), ProjectStatusEnum::Expired).into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Pending), 1);
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Expired), 1);
+    assert_eq!(client.expire_projects(&0, &10), None);
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Expired), 1);
+    // the expiry is recorded without an actor
+    let history = client.get_project_history(&project2_hash, &0, &10);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(1).unwrap().to, ProjectStatusEnum::Expired);
+    assert_eq!(history.get(1).unwrap().actor, None);
+
+    // an expired project can be reset with a new deadline
+    client.reset_project(&admin, &project2_hash, &RejectionReason::Other, &None);
+    assert_eq!(client.get_project_status(&project2_hash), ProjectStatusEnum::Pending);
+    assert_eq!(client.get_review_deadline(&project2_hash), Some(start + 201));
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Expired), 0);
+}
+
+#[test]
+fn test_expire_projects_in_pages() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let hashes: std::vec::Vec<BytesN<32>> = (1..=7u8).map(|i| BytesN::from_array(&env, &[i; 32])).collect();
+
+    // five overdue projects with two projects without a deadline among them
+    client.set_review_window(&10);
+    for (i, project_hash) in hashes.iter().enumerate() {
+        if i == 1 || i == 4 {
+            client.set_review_window(&0);
+            client.add_project(&user1, project_hash, &default_category(&env), &None);
+            client.set_review_window(&10);
+        } else {
+            client.add_project(&user1, project_hash, &default_category(&env), &None);
+        }
+    }
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+
+    // following the returned cursor visits every pending project once
+    let mut cursor = Some(0);
+    let mut sweeps = 0;
+    while let Some(start) = cursor {
+        cursor = client.expire_projects(&start, &2);
+        sweeps += 1;
+    }
+    assert_eq!(sweeps, 4);
+    // an empty page does not hand back the same cursor forever
+    assert_eq!(client.expire_projects(&0, &0), None);
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Expired), 5);
+    let pending = client.get_projects_by_status(&ProjectStatusEnum::Pending, &0, &10);
+    assert_eq!(pending.len(), 2);
+    assert!(pending.contains(ProjectData { hash: hashes[1].clone(), status: ProjectStatusEnum::Pending }));
+    assert!(pending.contains(ProjectData { hash: hashes[4].clone(), status: ProjectStatusEnum::Pending }));
+}
+
+#[test]
+fn test_reset_unswept_expired_project() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 10;
+        li.timestamp = 1000;
+    });
+    client.set_review_window(&100);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+
+    // the project expires without being swept, then it is reset
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 111;
+        li.timestamp = 2000;
+    });
+    assert_eq!(client.get_project_status(&project1_hash), ProjectStatusEnum::Expired);
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Pending), 1);
+    client.reset_project(&admin, &project1_hash, &RejectionReason::Other, &None);
+
+    // the expiry is recorded before the reset, without an actor
+    let change = |from: ProjectStatusEnum, to: ProjectStatusEnum, actor: Option<Address>, ledger: u32, timestamp: u64| StatusChange {
+        from,
+        to,
+        actor,
+        ledger,
+        timestamp,
+        reason_hash: None,
+    };
+    assert_eq!(
+        client.get_project_history(&project1_hash, &0, &10),
+        vec![
+            &env,
+            change(ProjectStatusEnum::NotSet, ProjectStatusEnum::Pending, Some(user1.clone()), 10, 1000),
+            change(ProjectStatusEnum::Pending, ProjectStatusEnum::Expired, None, 111, 2000),
+            change(ProjectStatusEnum::Expired, ProjectStatusEnum::Pending, Some(admin.clone()), 111, 2000),
+        ]
+    );
+    assert_eq!(client.get_project_status(&project1_hash), ProjectStatusEnum::Pending);
+    assert_eq!(client.get_review_deadline(&project1_hash), Some(211));
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Pending), 1);
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Expired), 0);
+}
+
+#[test]
+fn test_project_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let reviewer = whitelist_addresses.get(0).unwrap();
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let trufa_scores = TrufaScoreValues {
+        technical_feasibility: 70,
+        regulatory_compliance: 70,
+        financial_viability: 70,
+        environment_impact: 70,
+        overall_trufa_score: 70
+    };
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 10;
+        li.timestamp = 1000;
+    });
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 20;
+        li.timestamp = 2000;
+    });
+    client.set_project_rejected(&reviewer, &project1_hash, &RejectionReason::Other, &None);
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 30;
+        li.timestamp = 3000;
+    });
+    client.reset_project(&admin, &project1_hash, &RejectionReason::Other, &None);
+    client.set_project_approved(&reviewer, &project1_hash, &trufa_scores);
+
+    // the rejection is still known after the reset
+    let change = |from: ProjectStatusEnum, to: ProjectStatusEnum, actor: &Address, ledger: u32, timestamp: u64| StatusChange {
+        from,
+        to,
+        actor: Some(actor.clone()),
+        ledger,
+        timestamp,
+        reason_hash: None,
+    };
+    assert_eq!(client.get_project_history_length(&project1_hash), 4);
+    assert_eq!(
+        client.get_project_history(&project1_hash, &0, &10),
+        vec![
+            &env,
+            change(ProjectStatusEnum::NotSet, ProjectStatusEnum::Pending, &user1, 10, 1000),
+            change(ProjectStatusEnum::Pending, ProjectStatusEnum::Rejected, &reviewer, 20, 2000),
+            change(ProjectStatusEnum::Rejected, ProjectStatusEnum::Pending, &admin, 30, 3000),
+            change(ProjectStatusEnum::Pending, ProjectStatusEnum::Approved, &reviewer, 30, 3000),
+        ]
+    );
+    assert_eq!(
+        client.get_project_history(&project1_hash, &1, &1),
+        vec![&env, change(ProjectStatusEnum::Pending, ProjectStatusEnum::Rejected, &reviewer, 20, 2000)]
+    );
+    assert_eq!(client.get_project_history(&BytesN::from_array(&env, &[2; 32]), &0, &10).len(), 0);
+}
+
+#[test]
+fn test_rejection_rationale() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let reviewer = whitelist_addresses.get(0).unwrap();
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let rejection_document = BytesN::from_array(&env, &[10; 32]);
+    let reset_document = BytesN::from_array(&env, &[11; 32]);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    assert_eq!(client.get_rejection_rationale(&project1_hash), None);
+
+    // the rationale is stored with the project and sent in the event
+    client.set_project_rejected(&reviewer, &project1_hash, &RejectionReason::Regulatory, &Some(rejection_document.clone()));
+    let rejection = Rationale { reason: RejectionReason::Regulatory, document: Some(rejection_document.clone()) };
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("project", "rejected").into_val(&env),
+                (reviewer.clone(), project1_hash.clone(), ProjectStatusEnum::Rejected, rejection.clone()).into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(client.get_rejection_rationale(&project1_hash), Some(rejection.clone()));
+
+    // the reset has its own rationale and the rejection one is kept
+    client.reset_project(&admin, &project1_hash, &RejectionReason::Other, &Some(reset_document.clone()));
+    let reset = Rationale { reason: RejectionReason::Other, document: Some(reset_document.clone()) };
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("project", "reset").into_val(&env),
+                (admin.clone(), project1_hash.clone(), ProjectStatusEnum::Pending, reset.clone()).into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(client.get_reset_rationale(&project1_hash), Some(reset));
+    assert_eq!(client.get_rejection_rationale(&project1_hash), Some(rejection));
+
+    // and both documents are in the history
+    let history = client.get_project_history(&project1_hash, &0, &10);
+    assert_eq!(history.get(1).unwrap().reason_hash, Some(rejection_document));
+    assert_eq!(history.get(2).unwrap().reason_hash, Some(reset_document));
+}
+
+#[test]
+fn test_resubmit_project() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let user2 = addresses.get(6).unwrap(); // takes 7th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let reviewer = whitelist_addresses.get(0).unwrap();
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project1_v2_hash = BytesN::from_array(&env, &[2; 32]);
+    let project1_v3_hash = BytesN::from_array(&env, &[3; 32]);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    assert_eq!(client.get_project_versions(&project1_hash), vec![&env, project1_hash.clone()]);
+    assert_eq!(client.get_latest_version(&project1_hash), project1_hash);
+    let result = client.try_get_latest_version(&project1_v2_hash);
+    assert_eq!(result, Err(Ok(ProjectsError::ProjectNotFound)));
+
+    // only rejected projects can be resubmitted, by their submitter
+    let result = client.try_resubmit_project(&user1, &project1_hash, &project1_v2_hash, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::NotRejected)));
+    client.set_project_rejected(&reviewer, &project1_hash, &RejectionReason::Incomplete, &None);
+    let result = client.try_resubmit_project(&user2, &project1_hash, &project1_v2_hash, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::NotProjectOwner)));
+    let result = client.try_resubmit_project(&user1, &project1_hash, &project1_hash, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::ProjectAlreadyExists)));
+
+    client.resubmit_project(&user1, &project1_hash, &project1_v2_hash, &None);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "resubmit_project"),
+                    (&user1, &project1_hash, &project1_v2_hash, None::<ProjectMetadata>).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("project", "resubmitted").into_val(&env),
+                (user1.clone(), project1_hash.clone(), project1_v2_hash.clone()).into_val(&env)
+            )
+        ]
+    );
+
+    // the new version is pending for the same submitter and the old verdict is kept
+    assert_eq!(client.get_project_status(&project1_hash), ProjectStatusEnum::Rejected);
+    assert_eq!(client.get_project_status(&project1_v2_hash), ProjectStatusEnum::Pending);
+    assert_eq!(client.get_project_owner(&project1_v2_hash), user1);
+    let versions = vec![&env, project1_hash.clone(), project1_v2_hash.clone()];
+    assert_eq!(client.get_project_versions(&project1_hash), versions);
+    assert_eq!(client.get_project_versions(&project1_v2_hash), versions);
+    assert_eq!(client.get_latest_version(&project1_hash), project1_v2_hash);
+
+    // older versions can not be resubmitted again
+    client.set_project_rejected(&reviewer, &project1_v2_hash, &RejectionReason::Financial, &None);
+    let result = client.try_resubmit_project(&user1, &project1_hash, &project1_v3_hash, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::NotLatestVersion)));
+
+    // expired versions can be resubmitted too
+    client.set_review_window(&10);
+    client.reset_project(&admin, &project1_v2_hash, &RejectionReason::Other, &None);
+    let result = client.try_resubmit_project(&user1, &project1_v2_hash, &project1_v3_hash, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::NotRejected)));
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+    client.resubmit_project(&user1, &project1_v2_hash, &project1_v3_hash, &None);
+    assert_eq!(client.get_project_status(&project1_v2_hash), ProjectStatusEnum::Expired);
+    assert_eq!(client.count_by_status(&ProjectStatusEnum::Expired), 1);
+    // the expiry is recorded without an actor, like the sweep does
+    let history = client.get_project_history(&project1_v2_hash, &0, &10);
+    let expiry = history.last().unwrap();
+    assert_eq!(expiry.to, ProjectStatusEnum::Expired);
+    assert_eq!(expiry.actor, None);
+    assert_eq!(client.get_latest_version(&project1_hash), project1_v3_hash);
+    assert_eq!(client.get_project_versions(&project1_v3_hash).len(), 3);
+}
+
+#[test]
+fn test_project_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let user2 = addresses.get(6).unwrap(); // takes 7th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let reviewer = whitelist_addresses.get(0).unwrap();
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project1_v2_hash = BytesN::from_array(&env, &[2; 32]);
+    let project2_hash = BytesN::from_array(&env, &[3; 32]);
+    let metadata = ProjectMetadata {
+        title: String::from_str(&env, "Solar farm"),
+        category: default_category(&env),
+        country_code: String::from_str(&env, "KE"),
+        document_uri: String::from_str(&env, "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"),
+        funding_requested: 2_500_000_000_000,
+    };
+
+    // the metadata is optional and checked at submission
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
+    assert_eq!(client.get_project_metadata(&project2_hash), None);
+    let invalid = ProjectMetadata { country_code: String::from_str(&env, "KEN"), ..metadata.clone() };
+    let result = client.try_add_project(&user1, &project1_hash, &default_category(&env), &Some(invalid.clone()));
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidMetadata)));
+    let invalid = ProjectMetadata { funding_requested: -1, ..metadata.clone() };
+    let result = client.try_add_project(&user1, &project1_hash, &default_category(&env), &Some(invalid));
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidMetadata)));
+    let invalid = ProjectMetadata { title: String::from_str(&env, ""), ..metadata.clone() };
+    let result = client.try_add_project(&user1, &project1_hash, &default_category(&env), &Some(invalid));
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidMetadata)));
+    client.add_project(&user1, &project1_hash, &default_category(&env), &Some(metadata.clone()));
+    assert_eq!(client.get_project_metadata(&project1_hash), Some(metadata.clone()));
+
+    // the owner can update it while the project is pending
+    let updated = ProjectMetadata { funding_requested: 2_000_000_000_000, ..metadata.clone() };
+    let result = client.try_update_project_metadata(&user2, &project1_hash, &updated);
+    assert_eq!(result, Err(Ok(ProjectsError::NotProjectOwner)));
+    client.update_project_metadata(&user1, &project1_hash, &updated);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "update_project_metadata"),
+                    (&user1, &project1_hash, updated.clone()).into_val(&env),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(client.get_project_metadata(&project1_hash), Some(updated.clone()));
+    client.set_project_rejected(&reviewer, &project1_hash, &RejectionReason::Financial, &None);
+    let result = client.try_update_project_metadata(&user1, &project1_hash, &metadata);
+    assert_eq!(result, Err(Ok(ProjectsError::NotPending)));
+
+    // a resubmission keeps the metadata of the previous version
+    client.resubmit_project(&user1, &project1_hash, &project1_v2_hash, &None);
+    assert_eq!(client.get_project_metadata(&project1_v2_hash), Some(updated));
+}
+
+#[test]
+fn test_project_categories() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let general_reviewer = whitelist_addresses.get(0).unwrap();
+    let energy_reviewer = whitelist_addresses.get(1).unwrap();
+    let energy = Symbol::new(&env, "energy");
+    let agriculture = Symbol::new(&env, "agriculture");
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project1_v2_hash = BytesN::from_array(&env, &[2; 32]);
+    let project2_hash = BytesN::from_array(&env, &[3; 32]);
+    let project3_hash = BytesN::from_array(&env, &[4; 32]);
+    let trufa_scores = TrufaScoreValues {
+        technical_feasibility: 30,
+        regulatory_compliance: 40,
+        financial_viability: 50,
+        environment_impact: 40,
+        overall_trufa_score: 40
+    };
+
+    // the admin manages the categories
+    client.add_category(&energy);
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("category", "added").into_val(&env),
+                (energy.clone(),).into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(client.try_add_category(&energy), Err(Ok(ProjectsError::InvalidCategory)));
+    assert_eq!(client.get_categories(), vec![&env, default_category(&env), energy.clone()]);
+
+    // projects are submitted in an existing category
+    let result = client.try_add_project(&user1, &project1_hash, &agriculture, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::UnknownCategory)));
+    let metadata = ProjectMetadata {
+        title: String::from_str(&env, "Solar farm"),
+        category: default_category(&env),
+        country_code: String::from_str(&env, "KE"),
+        document_uri: String::from_str(&env, "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"),
+        funding_requested: 2_500_000_000_000,
+    };
+    let result = client.try_add_project(&user1, &project1_hash, &energy, &Some(metadata.clone()));
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidMetadata)));
+    let metadata = ProjectMetadata { category: energy.clone(), ..metadata };
+    client.add_project(&user1, &project1_hash, &energy, &Some(metadata.clone()));
+    client.add_project(&user1, &project2_hash, &energy, &None);
+    client.add_project(&user1, &project3_hash, &default_category(&env), &None);
+    assert_eq!(client.get_project_category(&project1_hash), Some(energy.clone()));
+    assert_eq!(client.count_by_category(&energy), 2);
+    assert_eq!(
+        client.get_projects_by_category(&energy, &1, &10),
+        vec![&env, ProjectData { hash: project2_hash.clone(), status: ProjectStatusEnum::Pending }]
+    );
+    let updated = ProjectMetadata { category: default_category(&env), ..metadata };
+    let result = client.try_update_project_metadata(&user1, &project1_hash, &updated);
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidMetadata)));
+
+    // only the reviewers whitelisted for the category can review its projects
+    let result = client.try_set_project_approved(&energy_reviewer, &project1_hash, &trufa_scores);
+    assert_eq!(result, Err(Ok(ProjectsError::NotCategoryReviewer)));
+    let result = client.try_assign_reviewers(&project1_hash, &vec![&env, energy_reviewer.clone()]);
+    assert_eq!(result, Err(Ok(ProjectsError::NotCategoryReviewer)));
+    let result = client.try_add_category_reviewer(&admin, &agriculture, &energy_reviewer);
+    assert_eq!(result, Err(Ok(ProjectsError::UnknownCategory)));
+    client.add_category_reviewer(&admin, &energy, &energy_reviewer);
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("category", "reviewer_added").into_val(&env),
+                (energy.clone(), energy_reviewer.clone()).into_val(&env)
+            )
+        ]
+    );
+    assert!(client.is_category_reviewer(&energy, &energy_reviewer));
+    let result = client.try_set_project_rejected(&general_reviewer, &project1_hash, &RejectionReason::Technical, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::NotCategoryReviewer)));
+    client.set_project_rejected(&energy_reviewer, &project1_hash, &RejectionReason::Technical, &None);
+    client.set_project_approved(&general_reviewer, &project3_hash, &trufa_scores);
+
+    // a resubmission stays in the category of the previous version
+    client.resubmit_project(&user1, &project1_hash, &project1_v2_hash, &None);
+    assert_eq!(client.get_project_category(&project1_v2_hash), Some(energy.clone()));
+    assert_eq!(client.count_by_category(&energy), 3);
+
+    // removed reviewers can no longer review the category
+    client.remove_category_reviewer(&admin, &energy, &energy_reviewer);
+    assert!(!client.is_category_reviewer(&energy, &energy_reviewer));
+    let result = client.try_set_project_approved(&energy_reviewer, &project2_hash, &trufa_scores);
+    assert_eq!(result, Err(Ok(ProjectsError::NotCategoryReviewer)));
+
+    // a removed category refuses new projects but keeps its index
+    client.remove_category(&energy);
+    assert_eq!(client.try_remove_category(&energy), Err(Ok(ProjectsError::UnknownCategory)));
+    assert_eq!(client.get_categories(), vec![&env, default_category(&env)]);
+    let result = client.try_add_project(&user1, &BytesN::from_array(&env, &[5; 32]), &energy, &None);
+    assert_eq!(result, Err(Ok(ProjectsError::UnknownCategory)));
+    assert_eq!(client.get_projects_by_category(&energy, &0, &10).len(), 3);
+}
+
+#[test]
+fn test_submission_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let user2 = addresses.get(6).unwrap(); // takes 7th address
+    let treasurer = addresses.get(7).unwrap(); // takes 8th address
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let fee_token = env.register_stellar_asset_contract_v2(admin.clone());
+    let other_token = env.register_stellar_asset_contract_v2(admin.clone());
+    let token_client = token::Client::new(&env, &fee_token.address());
+    token::StellarAssetClient::new(&env, &fee_token.address()).mint(&user1, &1000);
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project1_v2_hash = BytesN::from_array(&env, &[2; 32]);
+    let project2_hash = BytesN::from_array(&env, &[3; 32]);
+    let project3_hash = BytesN::from_array(&env, &[4; 32]);
+
+    // submissions are free until the admin sets a fee
+    assert_eq!(client.get_submission_fee(), None);
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    assert_eq!(token_client.balance(&user1), 1000);
+    let result = client.try_set_submission_fee(&fee_token.address(), &-1);
+    assert_eq!(result, Err(Ok(ProjectsError::InvalidAmount)));
+    client.set_submission_fee(&fee_token.address(), &100);
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("fee", "updated").into_val(&env),
+                (fee_token.address(), 100_i128).into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(client.get_submission_fee(), Some(SubmissionFee { token: fee_token.address(), amount: 100 }));
+
+    // the fee moves from the submitter into the treasury
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "add_project"),
+                    (&user1, &project2_hash, default_category(&env), None::<ProjectMetadata>).into_val(&env),
+                )),
+                sub_invocations: std::vec![AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        fee_token.address(),
+                        Symbol::new(&env, "transfer"),
+                        (&user1, &client.address, 100_i128).into_val(&env),
+                    )),
+                    sub_invocations: std::vec![]
+                }]
+            }
+        )]
+    );
+    assert_eq!(token_client.balance(&user1), 900);
+    assert_eq!(token_client.balance(&client.address), 100);
+    assert_eq!(client.get_treasury_balance(), 100);
+    // submitters that can not pay are refused
+    assert!(client.try_add_project(&user2, &project3_hash, &default_category(&env), &None).is_err());
+    assert_eq!(client.get_project_status(&project3_hash), ProjectStatusEnum::NotSet);
+
+    // resubmissions pay the fee as well
+    client.set_project_rejected(&whitelist_addresses.get(0).unwrap(), &project1_hash, &RejectionReason::Incomplete, &None);
+    client.resubmit_project(&user1, &project1_hash, &project1_v2_hash, &None);
+    assert_eq!(token_client.balance(&user1), 800);
+    assert_eq!(client.get_treasury_balance(), 200);
+
+    // the token can not change while the treasury holds fees
+    let result = client.try_set_submission_fee(&other_token.address(), &100);
+    assert_eq!(result, Err(Ok(ProjectsError::FeeTokenInUse)));
+    client.set_submission_fee(&fee_token.address(), &50);
+
+    // the admin withdraws the fees
+    assert_eq!(client.try_withdraw_fees(&treasurer, &0), Err(Ok(ProjectsError::InvalidAmount)));
+    assert_eq!(client.try_withdraw_fees(&treasurer, &201), Err(Ok(ProjectsError::InsufficientTreasury)));
+    client.withdraw_fees(&treasurer, &150);
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("fee", "withdrawn").into_val(&env),
+                (treasurer.clone(), 150_i128).into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(token_client.balance(&treasurer), 150);
+    assert_eq!(client.get_treasury_balance(), 50);
+    client.withdraw_fees(&treasurer, &50);
+    assert_eq!(token_client.balance(&client.address), 0);
+    client.set_submission_fee(&other_token.address(), &10);
+}
+
+#[test]
+fn test_review_deposit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let addresses = create_addresses(&env, &10);
+    let admin = addresses.get(0).unwrap(); // takes 1st address
+    let whitelist_addresses = addresses.slice(1..5); // takes from 2nd to 5th address
+    let user1 = addresses.get(5).unwrap(); // takes 6th address
+    let reviewer = whitelist_addresses.get(0).unwrap();
+    let client = create_projects_contract(&env, &admin, whitelist_addresses.clone());
+    let fee_token = env.register_stellar_asset_contract_v2(admin.clone());
+    let other_token = env.register_stellar_asset_contract_v2(admin.clone());
+    let token_client = token::Client::new(&env, &fee_token.address());
+    token::StellarAssetClient::new(&env, &fee_token.address()).mint(&user1, &1000);
+    let project1_hash = BytesN::from_array(&env, &[1; 32]);
+    let project2_hash = BytesN::from_array(&env, &[2; 32]);
+    let project3_hash = BytesN::from_array(&env, &[3; 32]);
+    let project4_hash = BytesN::from_array(&env, &[4; 32]);
+    let trufa_scores = TrufaScoreValues {
+        technical_feasibility: 30,
+        regulatory_compliance: 40,
+        financial_viability: 50,
+        environment_impact: 40,
+        overall_trufa_score: 40
+    };
+
+    // deposits are posted in the fee token
+    assert_eq!(client.get_review_deposit(), DepositConfig { amount: 0, forfeit_bps: 0 });
+    assert_eq!(client.try_set_review_deposit(&200, &2500), Err(Ok(ProjectsError::FeeTokenNotSet)));
+    client.set_submission_fee(&fee_token.address(), &0);
+    assert_eq!(client.try_set_review_deposit(&-1, &2500), Err(Ok(ProjectsError::InvalidAmount)));
+    assert_eq!(client.try_set_review_deposit(&200, &10001), Err(Ok(ProjectsError::InvalidForfeitRatio)));
+    client.set_review_deposit(&200, &2500);
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("deposit", "updated").into_val(&env),
+                (200_i128, 2500_u32).into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(client.get_review_deposit(), DepositConfig { amount: 200, forfeit_bps: 2500 });
+
+    // every submission posts the deposit
+    client.add_project(&user1, &project1_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project2_hash, &default_category(&env), &None);
+    client.add_project(&user1, &project3_hash, &default_category(&env), &None);
+    assert_eq!(token_client.balance(&user1), 400);
+    assert_eq!(client.get_deposits_held(), 600);
+    assert_eq!(
+        client.get_project_deposit(&project1_hash),
+        Some(ProjectDeposit { depositor: user1.clone(), token: fee_token.address(), amount: 200 })
+    );
+    // the fee token can not change while deposits are held
+    let result = client.try_set_submission_fee(&other_token.address(), &0);
+    assert_eq!(result, Err(Ok(ProjectsError::FeeTokenInUse)));
+
+    // approval refunds the whole deposit
+    client.set_project_approved(&reviewer, &project1_hash, &trufa_scores);
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 2..events.len() - 1),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("deposit", "settled").into_val(&env),
+                (project1_hash.clone(), user1.clone(), 200_i128, 0_i128).into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(client.get_project_deposit(&project1_hash), None);
+    assert_eq!(token_client.balance(&user1), 600);
+
+    // rejection forfeits part of it to the treasury
+    client.set_project_rejected(&reviewer, &project2_hash, &RejectionReason::Financial, &None);
+    let events = env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 2..events.len() - 1),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                ("deposit", "settled").into_val(&env),
+                (project2_hash.clone(), user1.clone(), 150_i128, 50_i128).into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(token_client.balance(&user1), 750);
+    assert_eq!(client.get_treasury_balance(), 50);
+
+    // withdrawal and expiry refund it
+    client.withdraw_project(&user1, &project3_hash);
+    assert_eq!(token_client.balance(&user1), 950);
+    client.set_review_window(&10);
+    client.add_project(&user1, &project4_hash, &default_category(&env), &None);
+    assert_eq!(token_client.balance(&user1), 750);
+    env.ledger().with_mut(|li| li.sequence_number += 11);
+    assert_eq!(client.expire_projects(&0, &10), None);
+    assert_eq!(token_client.balance(&user1), 950);
+    assert_eq!(client.get_deposits_held(), 0);
+    assert_eq!(token_client.balance(&client.address), 50);
+
+    // the largest deposit can still be forfeited without overflowing
+    let max_amount = i128::MAX / 10000;
+    assert_eq!(client.try_set_review_deposit(&(max_amount + 1), &2500), Err(Ok(ProjectsError::InvalidAmount)));
+    client.set_review_deposit(&max_amount, &10000);
+    let project5_hash = BytesN::from_array(&env, &[5; 32]);
+    token::StellarAssetClient::new(&env, &fee_token.address()).mint(&user1, &max_amount);
+    client.add_project(&user1, &project5_hash, &default_category(&env), &None);
+    client.set_project_rejected(&reviewer, &project5_hash, &RejectionReason::Financial, &None);
+    assert_eq!(client.get_treasury_balance(), 50 + max_amount);
+    assert_eq!(token_client.balance(&user1), 950);
+}
+
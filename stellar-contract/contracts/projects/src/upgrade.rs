@@ -0,0 +1,22 @@
+use soroban_sdk::{BytesN, Env};
+
+use crate::storage_types::DataKey;
+
+// 1: project records in the instance storage
+// 2: project records in the persistent storage
+// 3: projects indexed by status
+pub(crate) const SCHEMA_VERSION: u32 = 3;
+
+pub fn read_schema_version(e: &Env) -> u32 {
+    // deployments created before the version was tracked use the first layout
+    e.storage().instance().get(&DataKey::SchemaVersion).unwrap_or(1)
+}
+
+pub fn write_schema_version(e: &Env, version: u32) {
+    e.storage().instance().set(&DataKey::SchemaVersion, &version);
+}
+
+pub fn update_wasm(e: &Env, new_wasm_hash: &BytesN<32>) {
+    // the storage is kept as it is, the new code is responsible for migrating it
+    e.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+}
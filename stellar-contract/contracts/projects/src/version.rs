@@ -0,0 +1,53 @@
+use soroban_sdk::{BytesN, Env, Vec};
+
+use crate::storage_types::DataKey;
+use crate::storage_types::{PERSISTENT_BUMP_AMOUNT, PERSISTENT_LIFETIME_THRESHOLD};
+
+// the root is the hash of the first version, projects never resubmitted are
+// their own root
+pub fn get_root(e: &Env, project_hash: &BytesN<32>) -> BytesN<32> {
+    let key = DataKey::VersionRoot(project_hash.clone());
+    if let Some(root) = e.storage().persistent().get::<_, BytesN<32>>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        root
+    } else {
+        project_hash.clone()
+    }
+}
+
+// every version of a project, oldest first
+pub fn get_versions(e: &Env, project_hash: &BytesN<32>) -> Vec<BytesN<32>> {
+    let root = get_root(e, project_hash);
+    let key = DataKey::Versions(root.clone());
+    if let Some(versions) = e.storage().persistent().get::<_, Vec<BytesN<32>>>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+        versions
+    } else {
+        Vec::from_array(e, [root])
+    }
+}
+
+pub fn get_latest(e: &Env, project_hash: &BytesN<32>) -> BytesN<32> {
+    let versions = get_versions(e, project_hash);
+    versions.last().unwrap_or(project_hash.clone())
+}
+
+pub fn add_version(e: &Env, previous_hash: &BytesN<32>, new_hash: &BytesN<32>) {
+    let root = get_root(e, previous_hash);
+    let mut versions = get_versions(e, previous_hash);
+    versions.push_back(new_hash.clone());
+    let versions_key = DataKey::Versions(root.clone());
+    e.storage().persistent().set(&versions_key, &versions);
+    e.storage()
+        .persistent()
+        .extend_ttl(&versions_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+    let root_key = DataKey::VersionRoot(new_hash.clone());
+    e.storage().persistent().set(&root_key, &root);
+    e.storage()
+        .persistent()
+        .extend_ttl(&root_key, PERSISTENT_LIFETIME_THRESHOLD, PERSISTENT_BUMP_AMOUNT);
+}
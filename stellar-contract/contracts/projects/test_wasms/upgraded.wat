@@ -0,0 +1,14 @@
+;; The contract installed by test_upgrade in place of the projects contract.
+;; It only exports version(), which returns 4 so the test can tell the new
+;; code is running. The test compiles this file with the wat crate; to get the
+;; .wasm by hand, run `wat2wasm upgraded.wat` (wabt) or
+;; `wasm-tools parse upgraded.wat -o upgraded.wasm`.
+(module
+  ;; SCEnvMetaEntry::InterfaceVersion, XDR encoded: the kind (0), then the
+  ;; protocol (22) and the pre-release (0) versions the host requires
+  (@custom "contractenvmetav0" (before first) "\00\00\00\00" "\00\00\00\16" "\00\00\00\00")
+
+  ;; returns the u32 4 as a Val: the value in the upper 32 bits, the U32
+  ;; tag (4) in the lower bits
+  (func (export "version") (result i64)
+    i64.const 0x4_0000_0004))